@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use aoc_support::Solution;
+
+use crate::measure::{measure, Stage, Timing};
+
+/// Benchmarks every stage of a day on the given input.
+pub type Bench = fn(&str, Duration) -> color_eyre::Result<Vec<(Stage, Timing)>>;
+
+pub struct Day {
+    pub day: u8,
+    pub bench: Bench,
+}
+
+fn bench<S: Solution>(input: &str, budget: Duration) -> color_eyre::Result<Vec<(Stage, Timing)>> {
+    let params = S::Params::default();
+    let parsed = S::parse(input)?;
+
+    let mut timings = vec![
+        (Stage::Parse, measure(budget, || S::parse(input))?),
+        (
+            Stage::Part1,
+            measure(budget, || S::part1(&parsed, &params))?,
+        ),
+    ];
+    if S::HAS_PART2 {
+        timings.push((
+            Stage::Part2,
+            measure(budget, || S::part2(&parsed, &params))?,
+        ));
+    }
+
+    Ok(timings)
+}
+
+const fn day<S: Solution>() -> Day {
+    Day {
+        day: S::DAY,
+        bench: bench::<S>,
+    }
+}
+
+// `DAYS`, with every `aocN` dependency in the manifest (see
+// `aoc_support::registry`)
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+pub fn find(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
@@ -0,0 +1,178 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+
+use crate::measure::{Stage, Timing};
+
+/// Timing of one stage of one day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: u8,
+    pub stage: Stage,
+    #[serde(flatten)]
+    pub timing: Timing,
+}
+
+/// All the results of one benchmark run, saved as `run-<timestamp>.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    /// Milliseconds since the UNIX epoch
+    pub timestamp: u128,
+    pub records: Vec<Record>,
+}
+
+/// A stage that got slower than allowed since the previous run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub day: u8,
+    pub stage: Stage,
+    pub before_ns: u64,
+    pub after_ns: u64,
+}
+
+impl Regression {
+    pub fn percent(&self) -> f64 {
+        percent_change(self.before_ns, self.after_ns)
+    }
+}
+
+pub fn percent_change(before_ns: u64, after_ns: u64) -> f64 {
+    (after_ns as f64 - before_ns as f64) * 100.0 / before_ns.max(1) as f64
+}
+
+fn timestamp_of(path: &Path) -> Option<u128> {
+    path.file_name()?
+        .to_str()?
+        .strip_prefix("run-")?
+        .strip_suffix(".json")?
+        .parse()
+        .ok()
+}
+
+impl Run {
+    /// The most recent run saved in `dir`, if any.
+    pub fn latest(dir: &Path) -> color_eyre::Result<Option<Run>> {
+        if !dir.exists() {
+            return Ok(None);
+        }
+
+        let latest = std::fs::read_dir(dir)
+            .wrap_err_with(|| format!("Cannot list {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter_map(|path| timestamp_of(&path).map(|t| (t, path)))
+            .max();
+
+        let Some((_, path)) = latest else {
+            return Ok(None);
+        };
+
+        let s = std::fs::read_to_string(&path)
+            .wrap_err_with(|| format!("Cannot read {}", path.display()))?;
+        let run = serde_json::from_str(&s)
+            .wrap_err_with(|| format!("Invalid results in {}", path.display()))?;
+        Ok(Some(run))
+    }
+
+    pub fn save(&self, dir: &Path) -> color_eyre::Result<PathBuf> {
+        std::fs::create_dir_all(dir)
+            .wrap_err_with(|| format!("Cannot create {}", dir.display()))?;
+
+        let path = dir.join(format!("run-{}.json", self.timestamp));
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .wrap_err_with(|| format!("Cannot write {}", path.display()))?;
+        Ok(path)
+    }
+
+    pub fn find(&self, day: u8, stage: Stage) -> Option<&Timing> {
+        self.records
+            .iter()
+            .find(|r| r.day == day && r.stage == stage)
+            .map(|r| &r.timing)
+    }
+
+    /// Stages whose median got more than `threshold` percent slower than in
+    /// `previous`. Stages missing from either run are not compared.
+    pub fn regressions(&self, previous: &Run, threshold: f64) -> Vec<Regression> {
+        self.records
+            .iter()
+            .filter_map(|r| {
+                let before = previous.find(r.day, r.stage)?;
+                Some(Regression {
+                    day: r.day,
+                    stage: r.stage,
+                    before_ns: before.median_ns,
+                    after_ns: r.timing.median_ns,
+                })
+            })
+            .filter(|r| r.percent() > threshold)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(day: u8, stage: Stage, median_ns: u64) -> Record {
+        Record {
+            day,
+            stage,
+            timing: Timing {
+                median_ns,
+                min_ns: median_ns,
+                samples: 10,
+            },
+        }
+    }
+
+    #[test]
+    fn regressions() {
+        let before = Run {
+            timestamp: 1,
+            records: vec![
+                record(1, Stage::Parse, 100),
+                record(1, Stage::Part1, 100),
+                record(2, Stage::Part2, 100),
+            ],
+        };
+        let after = Run {
+            timestamp: 2,
+            records: vec![
+                record(1, Stage::Parse, 105),
+                record(1, Stage::Part1, 150),
+                record(3, Stage::Part1, 1000),
+            ],
+        };
+
+        assert_eq!(
+            after.regressions(&before, 10.0),
+            vec![Regression {
+                day: 1,
+                stage: Stage::Part1,
+                before_ns: 100,
+                after_ns: 150
+            }]
+        );
+        assert_eq!(after.regressions(&before, 4.0).len(), 2);
+        assert!(after.regressions(&before, 50.0).is_empty());
+    }
+
+    #[test]
+    fn file_names() {
+        assert_eq!(timestamp_of(Path::new("h/run-1234.json")), Some(1234));
+        assert_eq!(timestamp_of(Path::new("h/run-x.json")), None);
+        assert_eq!(timestamp_of(Path::new("h/notes.txt")), None);
+    }
+
+    #[test]
+    fn json() {
+        let run = Run {
+            timestamp: 7,
+            records: vec![record(17, Stage::Part2, 42)],
+        };
+        let s = serde_json::to_string(&run).expect("serializes");
+        assert!(s.contains(r#""stage":"part2","median_ns":42"#));
+        assert_eq!(serde_json::from_str::<Run>(&s).expect("deserializes"), run);
+    }
+}
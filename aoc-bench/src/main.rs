@@ -0,0 +1,132 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aoc_support::{input, selection::DaySelection};
+use clap::Parser;
+use color_eyre::eyre::bail;
+
+mod days;
+mod history;
+mod measure;
+
+use history::{percent_change, Record, Run};
+
+#[derive(Parser)]
+#[command(about = "Benchmarks parsing and both parts of Advent of Code 2024 solutions")]
+struct Cli {
+    /// Day like `17`, range like `3-7`, comma-separated list of those, or `all`
+    #[arg(default_value = "all")]
+    days: DaySelection,
+
+    /// How long to keep re-running each stage, in milliseconds
+    #[arg(long, default_value_t = 1000)]
+    budget_ms: u64,
+
+    /// Where results of every run are kept
+    #[arg(long, default_value_os_t = default_history())]
+    history: PathBuf,
+
+    /// Flag stages whose median got slower than this many percent since the
+    /// previous run
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+fn default_history() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("bench-history")
+}
+
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
+
+    let cli = Cli::parse();
+    let budget = Duration::from_millis(cli.budget_ms);
+    let previous = Run::latest(&cli.history)?;
+
+    let mut run = Run {
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis(),
+        records: Vec::new(),
+    };
+    let mut failures = 0;
+
+    for day in cli
+        .days
+        .0
+        .iter()
+        .map(|d| days::find(*d).expect("selection is valid"))
+    {
+        let timings = input::read_file(&input::default_path(day.day))
+            .map_err(Into::into)
+            .and_then(|text| (day.bench)(&text, budget));
+
+        let timings = match timings {
+            Ok(timings) => timings,
+            Err(e) => {
+                println!("Day {:>2}: FAILED: {:#}", day.day, e);
+                failures += 1;
+                continue;
+            }
+        };
+
+        for (stage, timing) in timings {
+            let change = previous
+                .as_ref()
+                .and_then(|p| p.find(day.day, stage))
+                .map(|before| {
+                    format!(
+                        " ({:+.1}%)",
+                        percent_change(before.median_ns, timing.median_ns)
+                    )
+                })
+                .unwrap_or_default();
+
+            println!(
+                "Day {:>2} {}: {:>10.2?} median, {:>10.2?} min, {:>4} samples{}",
+                day.day,
+                stage,
+                Duration::from_nanos(timing.median_ns),
+                Duration::from_nanos(timing.min_ns),
+                timing.samples,
+                change
+            );
+            run.records.push(Record {
+                day: day.day,
+                stage,
+                timing,
+            });
+        }
+    }
+
+    let path = run.save(&cli.history)?;
+    println!("Results saved to {}", path.display());
+
+    let regressions = previous
+        .map(|p| run.regressions(&p, cli.threshold))
+        .unwrap_or_default();
+    for r in regressions.iter() {
+        println!(
+            "REGRESSION: Day {:>2} {}: {:.2?} -> {:.2?} ({:+.1}%)",
+            r.day,
+            r.stage,
+            Duration::from_nanos(r.before_ns),
+            Duration::from_nanos(r.after_ns),
+            r.percent()
+        );
+    }
+
+    if failures > 0 {
+        bail!("{} day(s) failed", failures);
+    }
+    if !regressions.is_empty() {
+        bail!(
+            "{} stage(s) got more than {}% slower",
+            regressions.len(),
+            cli.threshold
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Every stage runs at least this often, however slow it is
+const MIN_SAMPLES: usize = 3;
+
+/// Fast stages stop here even if there is time left
+const MAX_SAMPLES: usize = 1000;
+
+/// What is being timed for a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl std::fmt::Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        })
+    }
+}
+
+/// Timings of running one stage many times.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Timing {
+    pub median_ns: u64,
+    pub min_ns: u64,
+    pub samples: usize,
+}
+
+/// Runs `f` until `budget` is used up, within [MIN_SAMPLES] and
+/// [MAX_SAMPLES] runs.
+///
+/// Stops at the first error.
+pub fn measure<T>(
+    budget: Duration,
+    mut f: impl FnMut() -> color_eyre::Result<T>,
+) -> color_eyre::Result<Timing> {
+    let mut samples = Vec::new();
+    let start = Instant::now();
+
+    while samples.len() < MIN_SAMPLES || (samples.len() < MAX_SAMPLES && start.elapsed() < budget) {
+        let sample = Instant::now();
+        black_box(f()?);
+        samples.push(sample.elapsed().as_nanos() as u64);
+    }
+
+    samples.sort();
+    Ok(Timing {
+        median_ns: samples[samples.len() / 2],
+        min_ns: samples[0],
+        samples: samples.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::eyre;
+
+    use super::*;
+
+    #[test]
+    fn sample_counts() {
+        let t = measure(Duration::ZERO, || Ok(())).expect("no errors");
+        assert_eq!(t.samples, MIN_SAMPLES);
+        assert!(t.min_ns <= t.median_ns);
+
+        let t = measure(Duration::from_secs(60), || Ok(())).expect("no errors");
+        assert_eq!(t.samples, MAX_SAMPLES);
+
+        assert!(measure(Duration::ZERO, || Err::<(), _>(eyre!("broken"))).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+//! Procedural macros of `aoc-support`. Use them through its re-exports.
+
+use std::path::PathBuf;
+
+use proc_macro::TokenStream;
+
+/// Generates an rstest case for every part of every example in the day's
+/// `examples.toml` that has an answer, see `aoc_support::examples`. The
+/// day needs `rstest` as a dev-dependency.
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///
+///     aoc_support::example_tests!(Solver);
+/// }
+/// ```
+///
+/// Cases are named after the example, like
+/// `tests::examples::case_1_example_txt_part1`. The examples are read when
+/// compiling, so changing `examples.toml` rebuilds the tests.
+#[proc_macro]
+pub fn example_tests(input: TokenStream) -> TokenStream {
+    let code = tests(&input.to_string())
+        .unwrap_or_else(|message| format!("compile_error!({:?});", message));
+    code.parse().expect("generated code is valid")
+}
+
+/// Turns an example name like `example.txt` into part of a test name.
+fn identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            'a'..='z' | '0'..='9' => c,
+            'A'..='Z' => c.to_ascii_lowercase(),
+            _ => '_',
+        })
+        .collect::<String>()
+        .trim_matches('_')
+        .to_string()
+}
+
+fn tests(solver: &str) -> Result<String, String> {
+    let dir = std::env::var("CARGO_MANIFEST_DIR").map_err(|e| e.to_string())?;
+    let path = PathBuf::from(dir).join("examples.toml");
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let mut table = text
+        .parse::<toml::Table>()
+        .map_err(|e| format!("Invalid {}: {}", path.display(), e))?;
+    let Some(toml::Value::Array(examples)) = table.remove("example") else {
+        return Err(format!("Expected [[example]] tables in {}", path.display()));
+    };
+
+    // makes cargo rebuild the tests when the examples change
+    let mut code = format!(
+        "const _: &str = include_str!({:?});\n",
+        path.to_string_lossy()
+    );
+
+    let mut cases = String::new();
+    for (idx, example) in examples.iter().enumerate() {
+        let name = match example.get("file") {
+            Some(toml::Value::String(file)) => identifier(file),
+            _ => "inline".to_string(),
+        };
+
+        for part in [1, 2] {
+            if example.get(format!("part{}", part)).is_some() {
+                cases.push_str(&format!("#[case::{name}_part{part}({idx}, {part})]\n"));
+            }
+        }
+    }
+
+    // rstest needs at least one case
+    if !cases.is_empty() {
+        code.push_str(&format!(
+            "#[::rstest::rstest]
+            {cases}fn examples(#[case] index: usize, #[case] part: u8) {{
+                ::aoc_support::examples::check_example::<{solver}>(
+                    ::std::path::Path::new(env!(\"CARGO_MANIFEST_DIR\")),
+                    index,
+                    part,
+                );
+            }}\n"
+        ));
+    }
+
+    Ok(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers() {
+        assert_eq!(identifier("example.txt"), "example_txt");
+        assert_eq!(identifier("Larger-Example2.txt"), "larger_example2_txt");
+        assert_eq!(identifier("../shared/map.txt"), "shared_map_txt");
+    }
+}
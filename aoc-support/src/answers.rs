@@ -0,0 +1,135 @@
+use std::path::{Path, PathBuf};
+
+/// Confirmed answers of a day, kept in an `answers.toml` next to its
+/// `input.txt`:
+///
+/// ```toml
+/// part1 = 1234
+/// part2 = "bmn,jss,mvb"
+/// ```
+///
+/// Parts without a confirmed answer are left out.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum AnswersError {
+    #[error("Cannot read answers file {}", .0.display())]
+    File(PathBuf, #[source] std::io::Error),
+
+    #[error("Answers are not valid TOML")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("Answer {0:?} must be a string or an integer")]
+    InvalidValue(String),
+
+    #[error("Unknown key {0:?}, expected part1 or part2")]
+    UnknownKey(String),
+}
+
+impl Answers {
+    /// Where the answers for `input` are stored.
+    pub fn path_for(input: &Path) -> PathBuf {
+        input.with_file_name("answers.toml")
+    }
+
+    /// Loads the answers stored next to `input`. A missing file means no
+    /// answer is confirmed yet.
+    pub fn load_for(input: &Path) -> Result<Answers, AnswersError> {
+        let path = Self::path_for(input);
+        match std::fs::read_to_string(&path) {
+            Ok(s) => s.parse(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(AnswersError::File(path, e)),
+        }
+    }
+
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<toml::Table> for Answers {
+    type Error = AnswersError;
+
+    fn try_from(table: toml::Table) -> Result<Self, Self::Error> {
+        let mut answers = Answers::default();
+
+        for (key, value) in table {
+            let value = match value {
+                toml::Value::String(s) => s,
+                toml::Value::Integer(i) => i.to_string(),
+                _ => return Err(AnswersError::InvalidValue(key)),
+            };
+            match key.as_str() {
+                "part1" => answers.part1 = Some(value),
+                "part2" => answers.part2 = Some(value),
+                _ => return Err(AnswersError::UnknownKey(key)),
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = AnswersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Answers::try_from(s.parse::<toml::Table>()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            "part1 = 123\npart2 = \"a,b\""
+                .parse::<Answers>()
+                .expect("valid"),
+            Answers {
+                part1: Some("123".into()),
+                part2: Some("a,b".into())
+            }
+        );
+        assert_eq!(
+            "# nothing yet".parse::<Answers>().expect("valid"),
+            Answers::default()
+        );
+
+        assert!(matches!(
+            "part1 = 1.5".parse::<Answers>(),
+            Err(AnswersError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            "part3 = 1".parse::<Answers>(),
+            Err(AnswersError::UnknownKey(_))
+        ));
+        assert!(matches!(
+            "part1 = ".parse::<Answers>(),
+            Err(AnswersError::Toml(_))
+        ));
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(
+            Answers::path_for(Path::new("inputs/aoc3/input.txt")),
+            Path::new("inputs/aoc3/answers.toml")
+        );
+        assert_eq!(
+            Answers::load_for(Path::new("does/not/exist.txt")).expect("no answers"),
+            Answers::default()
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! What every day's binary does around its [Solution].
+//!
+//! With the `tracy` feature, spans are also sent to a Tracy profiler and
+//! parsing and each part show up as separate frames.
+
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+use crate::Solution;
+
+/// Runs `$body` inside a Tracy frame called `$name`, if a Tracy client is
+/// running.
+macro_rules! frame {
+    ($name:literal, $body:expr) => {{
+        #[cfg(feature = "tracy")]
+        let _frame = tracing_tracy::client::Client::running()
+            .map(|client| client.non_continuous_frame(tracing_tracy::client::frame_name!($name)));
+        $body
+    }};
+}
+
+/// Logs compactly to stdout, filtered by `RUST_LOG`.
+pub fn init_tracing() {
+    let stdout_log = tracing_subscriber::fmt::layer().compact();
+
+    let registry = tracing_subscriber::registry()
+        .with(stdout_log)
+        .with(EnvFilter::from_default_env());
+
+    #[cfg(feature = "tracy")]
+    let registry = registry.with(tracing_tracy::TracyLayer::default());
+
+    registry.init();
+}
+
+/// Parses `input` and prints the answer of every part of `S`, using the
+/// default params.
+pub fn solve<S: Solution>(input: &str) -> color_eyre::Result<()> {
+    let params = S::Params::default();
+    let parsed = frame!("parse", S::parse(input))?;
+
+    let s1 = frame!("part1", S::part1(&parsed, &params))?;
+    println!("Part 1: {}", s1);
+
+    if S::HAS_PART2 {
+        let s2 = frame!("part2", S::part2(&parsed, &params))?;
+        println!("Part 2: {}", s2);
+    }
+
+    Ok(())
+}
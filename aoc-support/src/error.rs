@@ -0,0 +1,152 @@
+use miette::SourceSpan;
+use nom::{error::ErrorKind, IResult, Parser};
+
+/// Reasons why a puzzle input could not be parsed.
+///
+/// Lines and columns are 1-based, like in an editor. Errors come from
+/// [parse_complete], [complete] and [ensure_consumed], which know the whole
+/// input, so they point into it when shown as a miette report.
+#[derive(thiserror::Error, miette::Diagnostic, Debug, PartialEq)]
+pub enum ProcessingError {
+    #[error("Failed to parse ({kind:?}) at line {line}, column {column}")]
+    Invalid {
+        kind: ErrorKind,
+        line: usize,
+        column: usize,
+        #[source_code]
+        input: String,
+        #[label("cannot parse this")]
+        span: SourceSpan,
+    },
+
+    #[error("Unparsed data remained at line {line}, column {column}")]
+    UnparsedData {
+        line: usize,
+        column: usize,
+        #[source_code]
+        input: String,
+        #[label("not part of the input format")]
+        span: SourceSpan,
+    },
+
+    #[error("Input ended early")]
+    Incomplete,
+}
+
+/// 1-based line and column of a byte offset.
+fn line_column(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line, column)
+}
+
+/// Checks a nom result whose remaining input is a suffix of `input`,
+/// requiring that all of `input` got parsed.
+///
+/// Useful when parsing does not start at the beginning of `input`, for
+/// example after a `map-parse` map.
+pub fn complete<'a, O>(input: &'a str, result: IResult<&'a str, O>) -> Result<O, ProcessingError> {
+    let (rest, output) = match result {
+        Ok(parsed) => parsed,
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            let offset = input.len() - e.input.len();
+            let (line, column) = line_column(input, offset);
+            let len = e.input.chars().next().map_or(0, char::len_utf8);
+            return Err(ProcessingError::Invalid {
+                kind: e.code,
+                line,
+                column,
+                input: input.into(),
+                span: (offset, len).into(),
+            });
+        }
+        Err(nom::Err::Incomplete(_)) => return Err(ProcessingError::Incomplete),
+    };
+
+    ensure_consumed(input, rest)?;
+    Ok(output)
+}
+
+/// Fails with [ProcessingError::UnparsedData] unless `rest`, the suffix of
+/// `input` left after parsing, is empty.
+pub fn ensure_consumed(input: &str, rest: &str) -> Result<(), ProcessingError> {
+    if rest.is_empty() {
+        return Ok(());
+    }
+
+    // point at the data itself, not at the line break before it
+    let data = rest.trim_start_matches(['\r', '\n']);
+    let offset = input.len() - data.len();
+    let (line, column) = line_column(input, offset);
+    let len = data.find(['\r', '\n']).unwrap_or(data.len());
+    Err(ProcessingError::UnparsedData {
+        line,
+        column,
+        input: input.into(),
+        span: (offset, len).into(),
+    })
+}
+
+/// Runs `parser` on all of `input`. Data left over is an error.
+pub fn parse_complete<'a, O>(
+    mut parser: impl Parser<&'a str, O, nom::error::Error<&'a str>>,
+    input: &'a str,
+) -> Result<O, ProcessingError> {
+    complete(input, parser.parse(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{self, line_ending},
+        multi::separated_list1,
+        sequence::separated_pair,
+    };
+
+    use super::*;
+
+    fn pairs(s: &str) -> Result<Vec<(u32, u32)>, ProcessingError> {
+        parse_complete(
+            separated_list1(
+                line_ending,
+                separated_pair(complete::u32, tag(","), complete::u32),
+            ),
+            s,
+        )
+    }
+
+    #[test]
+    fn complete_input() {
+        assert_eq!(pairs("1,2\n3,4"), Ok(vec![(1, 2), (3, 4)]));
+    }
+
+    #[test]
+    fn error_positions() {
+        assert!(matches!(
+            pairs("1;2\n3,4"),
+            Err(ProcessingError::Invalid {
+                kind: ErrorKind::Tag,
+                line: 1,
+                column: 2,
+                ..
+            })
+        ));
+
+        let err = pairs("1,2\n3,4\nabc\n").expect_err("unparsed data");
+        assert_eq!(
+            err,
+            ProcessingError::UnparsedData {
+                line: 3,
+                column: 1,
+                input: "1,2\n3,4\nabc\n".into(),
+                span: (8, 3).into(),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Unparsed data remained at line 3, column 1"
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+
+use crate::{
+    answers::{Answers, AnswersError},
+    Solution,
+};
+
+/// The examples of a day's puzzle, kept as data in an `examples.toml` next
+/// to its `input.txt`:
+///
+/// ```toml
+/// [[example]]
+/// file = "example.txt"
+/// params = { width = 11, height = 7 }
+/// part1 = 12
+///
+/// [[example]]
+/// input = "AAAA\nBBCD\nBBCC\nEEEC"
+/// part2 = 80
+/// ```
+///
+/// The input is either a `file` relative to the day, or given inline as
+/// `input`. `params` are the day's [Solution::Params], which default when
+/// left out. Answers are written like in `answers.toml`.
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    /// The file name, or `#N` for inline inputs
+    pub name: String,
+    pub input: String,
+    pub params: Option<toml::Value>,
+    pub answers: Answers,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ExamplesError {
+    #[error("Cannot read {}", .0.display())]
+    File(PathBuf, #[source] std::io::Error),
+
+    #[error("Examples are not valid TOML")]
+    Toml(#[from] toml::de::Error),
+
+    #[error("Expected a list of [[example]] tables")]
+    NotAList,
+
+    #[error("Example #{0} needs either a file or an input")]
+    MissingInput(usize),
+
+    #[error("Invalid answers in {0}")]
+    Answers(String, #[source] AnswersError),
+}
+
+/// Loads the `examples.toml` in `dir`.
+pub fn load(dir: &Path) -> Result<Vec<Example>, ExamplesError> {
+    let path = dir.join("examples.toml");
+    let s = std::fs::read_to_string(&path).map_err(|e| ExamplesError::File(path, e))?;
+    parse(&s, dir)
+}
+
+/// Parses examples, reading the example files relative to `dir`.
+pub fn parse(s: &str, dir: &Path) -> Result<Vec<Example>, ExamplesError> {
+    let mut table = s.parse::<toml::Table>()?;
+
+    let Some(toml::Value::Array(examples)) = table.remove("example") else {
+        return Err(ExamplesError::NotAList);
+    };
+    if !table.is_empty() {
+        return Err(ExamplesError::NotAList);
+    }
+
+    examples
+        .into_iter()
+        .enumerate()
+        .map(|(idx, example)| {
+            let toml::Value::Table(mut example) = example else {
+                return Err(ExamplesError::NotAList);
+            };
+
+            let (name, input) = match (example.remove("file"), example.remove("input")) {
+                (Some(toml::Value::String(file)), None) => {
+                    let path = dir.join(&file);
+                    let input =
+                        std::fs::read_to_string(&path).map_err(|e| ExamplesError::File(path, e))?;
+                    (file, input)
+                }
+                (None, Some(toml::Value::String(input))) => (format!("#{}", idx + 1), input),
+                _ => return Err(ExamplesError::MissingInput(idx + 1)),
+            };
+            let params = example.remove("params");
+            let answers =
+                Answers::try_from(example).map_err(|e| ExamplesError::Answers(name.clone(), e))?;
+
+            Ok(Example {
+                name,
+                input,
+                params,
+                answers,
+            })
+        })
+        .collect()
+}
+
+/// Solves `part` of the example at `index` in `dir`, panicking if the
+/// answer is wrong.
+///
+/// Meant for the tests generated by [crate::example_tests].
+pub fn check_example<S: Solution>(dir: &Path, index: usize, part: u8)
+where
+    S::Params: DeserializeOwned,
+{
+    assert!(
+        part == 1 || S::HAS_PART2,
+        "Day {} has no part {}, but its examples give an answer for it",
+        S::DAY,
+        part
+    );
+
+    let mut examples =
+        load(dir).unwrap_or_else(|e| panic!("Invalid examples in {}: {:?}", dir.display(), e));
+    assert!(index < examples.len(), "No example #{}", index + 1);
+    let example = examples.swap_remove(index);
+
+    let expected = example
+        .answers
+        .part(part)
+        .unwrap_or_else(|| panic!("{} has no answer for part {}", example.name, part));
+
+    let params = match example.params {
+        Some(params) => params
+            .try_into()
+            .unwrap_or_else(|e| panic!("Invalid params for {}: {}", example.name, e)),
+        None => S::Params::default(),
+    };
+
+    let answer = match part {
+        1 => S::solve1(&example.input, &params).map(|a| a.to_string()),
+        _ => S::solve2(&example.input, &params).map(|a| a.to_string()),
+    }
+    .unwrap_or_else(|e| panic!("{} part {} failed: {:?}", example.name, part, e));
+
+    assert_eq!(answer, expected, "{} part {}", example.name, part);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_examples() {
+        let examples = parse(
+            r#"
+            [[example]]
+            input = "1 2"
+            params = { size = 3 }
+            part1 = 3
+
+            [[example]]
+            input = "4"
+            part2 = "four"
+            "#,
+            Path::new("."),
+        )
+        .expect("valid examples");
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].name, "#1");
+        assert_eq!(examples[0].input, "1 2");
+        assert!(examples[0].params.is_some());
+        assert_eq!(examples[0].answers.part(1), Some("3"));
+        assert_eq!(examples[1].answers.part(1), None);
+        assert_eq!(examples[1].answers.part(2), Some("four"));
+    }
+
+    #[test]
+    fn invalid_examples() {
+        let dir = Path::new(".");
+        assert!(matches!(
+            parse("part1 = 1", dir),
+            Err(ExamplesError::NotAList)
+        ));
+        assert!(matches!(
+            parse("[[example]]\npart1 = 1", dir),
+            Err(ExamplesError::MissingInput(1))
+        ));
+        assert!(matches!(
+            parse("[[example]]\nfile = \"missing.txt\"", dir),
+            Err(ExamplesError::File(..))
+        ));
+        assert!(matches!(
+            parse("[[example]]\ninput = \"\"\nparts = 1", dir),
+            Err(ExamplesError::Answers(..))
+        ));
+    }
+}
@@ -0,0 +1,97 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// Environment variable naming a directory with an `aocN/input.txt` for
+/// every day, laid out like this repository.
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS";
+
+#[derive(thiserror::Error, Debug)]
+pub enum InputError {
+    #[error("Cannot read input file {}", .path.display())]
+    File {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Cannot read input from stdin")]
+    Stdin(#[source] std::io::Error),
+
+    #[error("No input for day {0}: pass a file path, - for stdin or set {INPUTS_DIR_VAR}")]
+    Missing(u8),
+}
+
+/// Where the input of `day` lives inside an inputs directory.
+pub fn input_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("aoc{}", day)).join("input.txt")
+}
+
+pub fn read_file(path: &Path) -> Result<String, InputError> {
+    std::fs::read_to_string(path).map_err(|source| InputError::File {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Path of the input of `day` inside the directory named by [INPUTS_DIR_VAR].
+pub fn inputs_dir_path(day: u8) -> Result<PathBuf, InputError> {
+    std::env::var_os(INPUTS_DIR_VAR)
+        .map(|dir| input_path(Path::new(&dir), day))
+        .ok_or(InputError::Missing(day))
+}
+
+/// Path of the input of `day` for tools running many days: the one in
+/// [INPUTS_DIR_VAR], or else the one checked in next to the day.
+pub fn default_path(day: u8) -> PathBuf {
+    inputs_dir_path(day)
+        .unwrap_or_else(|_| input_path(&Path::new(env!("CARGO_MANIFEST_DIR")).join(".."), day))
+}
+
+/// Reads the input of `day` from the directory named by [INPUTS_DIR_VAR].
+///
+/// Used by benches, which have neither arguments nor stdin to spare.
+pub fn from_inputs_dir(day: u8) -> Result<String, InputError> {
+    read_file(&inputs_dir_path(day)?)
+}
+
+/// Reads the input of a day binary.
+///
+/// It uses the file given as the first command line argument, stdin if
+/// that argument is `-`, or else the day's file in [INPUTS_DIR_VAR]. Stdin
+/// is only read when asked for, so binaries started with an open but idle
+/// stdin (under CI or nohup) do not block.
+pub fn load(day: u8) -> Result<String, InputError> {
+    match std::env::args_os().nth(1) {
+        Some(arg) if arg == "-" => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(InputError::Stdin)?;
+            Ok(input)
+        }
+        Some(path) => read_file(Path::new(&path)),
+        None => from_inputs_dir(day),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths() {
+        assert_eq!(
+            input_path(Path::new("inputs"), 17),
+            Path::new("inputs/aoc17/input.txt")
+        );
+    }
+
+    #[test]
+    fn missing_file() {
+        let err = read_file(Path::new("does/not/exist.txt")).expect_err("no such file");
+        assert!(matches!(err, InputError::File { .. }));
+        assert_eq!(err.to_string(), "Cannot read input file does/not/exist.txt");
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt::Display;
+
+pub mod answers;
+pub mod binary;
+pub mod error;
+pub mod examples;
+pub mod input;
+pub mod registry;
+pub mod selection;
+pub mod testing;
+
+pub use aoc_macros::example_tests;
+
+/// A day's puzzle solution, split into parsing the input and solving
+/// each part on the parsed input.
+///
+/// Every day implements this, so that tooling (the runner, benches, tests)
+/// can drive all days the same way.
+pub trait Solution {
+    /// Day of the month, 1 to 25
+    const DAY: u8;
+
+    /// Day 25 only has one part. Its `part2` is never called.
+    const HAS_PART2: bool = true;
+
+    /// The parsed puzzle input
+    type Input;
+
+    type Answer1: Display;
+    type Answer2: Display;
+
+    /// Knobs that differ between the examples and the real input, like a
+    /// grid size. The default is what the real input needs.
+    type Params: Default;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input>;
+
+    fn part1(input: &Self::Input, params: &Self::Params) -> color_eyre::Result<Self::Answer1>;
+
+    fn part2(input: &Self::Input, params: &Self::Params) -> color_eyre::Result<Self::Answer2>;
+
+    /// Parses `input` and solves part 1 on it.
+    fn solve1(input: &str, params: &Self::Params) -> color_eyre::Result<Self::Answer1> {
+        Self::part1(&Self::parse(input)?, params)
+    }
+
+    /// Parses `input` and solves part 2 on it.
+    fn solve2(input: &str, params: &Self::Params) -> color_eyre::Result<Self::Answer2> {
+        Self::part2(&Self::parse(input)?, params)
+    }
+}
+
+/// The answer of a part that does not exist (see [Solution::HAS_PART2]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoAnswer;
+
+impl Display for NoAnswer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("-")
+    }
+}
@@ -0,0 +1,104 @@
+//! The list of days of tools that run all of them (the runner, the
+//! benchmarks), generated by their build scripts from the `aocN`
+//! dependencies in their manifest.
+//!
+//! Registering a day with such a tool only needs the dependency:
+//!
+//! ```toml
+//! aoc26 = { path = "../aoc26" }
+//! ```
+//!
+//! The tool then includes the generated `DAYS` constant, having a generic
+//! `const fn day<S: Solution>() -> Day` in scope:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/days.rs"));
+//! ```
+
+use std::path::{Path, PathBuf};
+
+#[derive(thiserror::Error, Debug)]
+pub enum RegistryError {
+    #[error("Cannot access {}", .0.display())]
+    File(PathBuf, #[source] std::io::Error),
+
+    #[error("Manifest is not valid TOML")]
+    Toml(#[from] toml::de::Error),
+}
+
+/// Days of the `aocN` dependencies in a crate manifest, in order.
+pub fn days_in(manifest: &str) -> Result<Vec<u8>, toml::de::Error> {
+    let manifest = manifest.parse::<toml::Table>()?;
+
+    let mut days = manifest
+        .get("dependencies")
+        .and_then(|deps| deps.as_table())
+        .into_iter()
+        .flat_map(|deps| deps.keys())
+        .filter_map(|name| name.strip_prefix("aoc")?.parse::<u8>().ok())
+        .collect::<Vec<_>>();
+    days.sort();
+
+    Ok(days)
+}
+
+/// Source of a `DAYS` array with a `day::<aocN::Solver>()` for every day.
+pub fn days_source(days: &[u8]) -> String {
+    let mut s = String::from("/// All the days, in order.\n");
+    s.push_str(&format!("pub const DAYS: [Day; {}] = [\n", days.len()));
+    for day in days {
+        s.push_str(&format!("    day::<aoc{}::Solver>(),\n", day));
+    }
+    s.push_str("];\n");
+    s
+}
+
+/// Writes `days.rs` for the calling build script's crate into `OUT_DIR`.
+pub fn write_days() -> Result<(), RegistryError> {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").expect("run by cargo");
+    let out_dir = std::env::var_os("OUT_DIR").expect("run from a build script");
+
+    let manifest = Path::new(&manifest_dir).join("Cargo.toml");
+    println!("cargo:rerun-if-changed={}", manifest.display());
+
+    let s = std::fs::read_to_string(&manifest).map_err(|e| RegistryError::File(manifest, e))?;
+    let source = days_source(&days_in(&s)?);
+
+    let path = Path::new(&out_dir).join("days.rs");
+    std::fs::write(&path, source).map_err(|e| RegistryError::File(path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_from_dependencies() {
+        let days = days_in(
+            r#"
+            [package]
+            name = "aoc"
+
+            [dependencies]
+            aoc-support = { path = "../aoc-support" }
+            clap.workspace = true
+            aoc10 = { path = "../aoc10" }
+            aoc2 = { path = "../aoc2" }
+
+            [dev-dependencies]
+            aoc3 = { path = "../aoc3" }
+            "#,
+        )
+        .expect("valid manifest");
+        assert_eq!(days, vec![2, 10]);
+
+        assert_eq!(
+            days_source(&days),
+            "/// All the days, in order.\n\
+             pub const DAYS: [Day; 2] = [\n    \
+             day::<aoc2::Solver>(),\n    \
+             day::<aoc10::Solver>(),\n\
+             ];\n"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::str::FromStr;
+
+/// Which days to run: `all`, a single day like `17`, a range like `3-7`,
+/// or a comma-separated list of those.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DaySelection(pub Vec<u8>);
+
+const FIRST_DAY: u8 = 1;
+const LAST_DAY: u8 = 25;
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    match s.trim().parse::<u8>() {
+        Ok(day) if (FIRST_DAY..=LAST_DAY).contains(&day) => Ok(day),
+        _ => Err(format!(
+            "{:?} is not a day between {} and {}",
+            s, FIRST_DAY, LAST_DAY
+        )),
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut days = Vec::new();
+
+        for item in s.split(',') {
+            match item.trim() {
+                "all" => days.extend(FIRST_DAY..=LAST_DAY),
+                item => match item.split_once('-') {
+                    Some((first, last)) => {
+                        let (first, last) = (parse_day(first)?, parse_day(last)?);
+                        if first > last {
+                            return Err(format!("{:?} is an empty range", item));
+                        }
+                        days.extend(first..=last);
+                    }
+                    None => days.push(parse_day(item)?),
+                },
+            }
+        }
+
+        days.sort();
+        days.dedup();
+        Ok(DaySelection(days))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selections() {
+        assert_eq!("17".parse(), Ok(DaySelection(vec![17])));
+        assert_eq!("3-5".parse(), Ok(DaySelection(vec![3, 4, 5])));
+        assert_eq!("9,1-2,2".parse(), Ok(DaySelection(vec![1, 2, 9])));
+        assert_eq!("all".parse::<DaySelection>().map(|s| s.0.len()), Ok(25));
+
+        assert!("0".parse::<DaySelection>().is_err());
+        assert!("26".parse::<DaySelection>().is_err());
+        assert!("5-3".parse::<DaySelection>().is_err());
+        assert!("x".parse::<DaySelection>().is_err());
+    }
+}
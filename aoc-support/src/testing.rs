@@ -0,0 +1,11 @@
+use std::sync::Once;
+
+static INIT: Once = Once::new();
+
+/// Installs color-eyre once for all tests of a crate, so failing tests
+/// show nicely formatted reports.
+pub fn init_tests() {
+    INIT.call_once(|| {
+        color_eyre::install().unwrap_or(());
+    });
+}
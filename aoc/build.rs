@@ -0,0 +1,3 @@
+fn main() {
+    aoc_support::registry::write_days().expect("days to register");
+}
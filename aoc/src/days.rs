@@ -0,0 +1,93 @@
+use aoc_support::Solution;
+
+/// Solves one part of a puzzle, returning the answer formatted for printing.
+pub type Part = fn(&str) -> color_eyre::Result<String>;
+
+/// Runs parsing and then every part of a puzzle, each through a
+/// [StageObserver].
+pub type Stages = fn(&str, &mut dyn StageObserver) -> color_eyre::Result<()>;
+
+/// Wraps every stage of solving a day, for example to measure it.
+pub trait StageObserver {
+    /// Runs a stage (`parse`, `part1` or `part2`) of a day.
+    fn stage(
+        &mut self,
+        name: &'static str,
+        run: &mut dyn FnMut() -> color_eyre::Result<()>,
+    ) -> color_eyre::Result<()>;
+}
+
+/// Everything the runner knows about a single day.
+pub struct Day {
+    pub day: u8,
+    pub part1: Part,
+    /// Missing for days with only one puzzle (i.e. day 25)
+    pub part2: Option<Part>,
+    pub stages: Stages,
+}
+
+impl Day {
+    pub fn part(&self, part: u8) -> Option<Part> {
+        match part {
+            1 => Some(self.part1),
+            2 => self.part2,
+            _ => None,
+        }
+    }
+}
+
+fn part1<S: Solution>(input: &str) -> color_eyre::Result<String> {
+    Ok(S::solve1(input, &S::Params::default())?.to_string())
+}
+
+fn part2<S: Solution>(input: &str) -> color_eyre::Result<String> {
+    Ok(S::solve2(input, &S::Params::default())?.to_string())
+}
+
+fn stages<S: Solution>(input: &str, observer: &mut dyn StageObserver) -> color_eyre::Result<()> {
+    let params = S::Params::default();
+
+    let mut parsed = None;
+    observer.stage("parse", &mut || {
+        parsed = Some(S::parse(input)?);
+        Ok(())
+    })?;
+    let parsed = parsed.expect("parsed input");
+
+    observer.stage("part1", &mut || S::part1(&parsed, &params).map(drop))?;
+    if S::HAS_PART2 {
+        observer.stage("part2", &mut || S::part2(&parsed, &params).map(drop))?;
+    }
+    Ok(())
+}
+
+/// Runs a day with the parameters of the real puzzle input.
+const fn day<S: Solution>() -> Day {
+    Day {
+        day: S::DAY,
+        part1: part1::<S>,
+        part2: if S::HAS_PART2 { Some(part2::<S>) } else { None },
+        stages: stages::<S>,
+    }
+}
+
+// `DAYS`, with every `aocN` dependency in the manifest (see
+// `aoc_support::registry`)
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+pub fn find(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_in_order() {
+        for (idx, day) in DAYS.iter().enumerate() {
+            assert_eq!(day.day as usize, idx + 1);
+        }
+        assert!(find(25).expect("day 25 exists").part2.is_none());
+    }
+}
@@ -0,0 +1,73 @@
+use crate::days::StageObserver;
+
+/// Heap usage of a single stage of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeapUsage {
+    pub total_bytes: u64,
+    pub peak_bytes: usize,
+    pub allocations: u64,
+}
+
+/// Profiles every stage on its own, so peaks of earlier stages do not hide
+/// the ones of later stages.
+///
+/// Needs the `dhat-heap` feature, which installs the allocator doing the
+/// counting.
+#[derive(Default)]
+pub struct HeapProfiler {
+    pub stages: Vec<(&'static str, HeapUsage)>,
+}
+
+impl StageObserver for HeapProfiler {
+    fn stage(
+        &mut self,
+        name: &'static str,
+        run: &mut dyn FnMut() -> color_eyre::Result<()>,
+    ) -> color_eyre::Result<()> {
+        let profiler = dhat::Profiler::builder().testing().build();
+        let result = run();
+        let stats = dhat::HeapStats::get();
+        drop(profiler);
+
+        result?;
+        self.stages.push((
+            name,
+            HeapUsage {
+                total_bytes: stats.total_bytes,
+                peak_bytes: stats.max_bytes,
+                allocations: stats.total_blocks,
+            },
+        ));
+        Ok(())
+    }
+}
+
+/// Byte counts in the largest unit that keeps them above 1.
+pub fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_units() {
+        assert_eq!(human_bytes(0), "0 B");
+        assert_eq!(human_bytes(1023), "1023 B");
+        assert_eq!(human_bytes(1536), "1.5 KiB");
+        assert_eq!(human_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,273 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use aoc_support::{answers::Answers, input, selection::DaySelection};
+use clap::{Parser, Subcommand};
+use color_eyre::eyre::{bail, eyre, WrapErr};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+mod days;
+mod heap;
+mod scaffold;
+mod verify;
+
+use heap::{human_bytes, HeapProfiler};
+use verify::{Summary, Verdict};
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[derive(Parser)]
+#[command(about = "Runs Advent of Code 2024 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs the given days, printing every answer with its timing
+    Run {
+        /// Day like `17`, range like `3-7`, comma-separated list of those, or `all`
+        days: DaySelection,
+
+        /// Only run this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Input file to use instead of the day's `input.txt` (single day only)
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+
+    /// Runs the given days, checking every answer against the confirmed one
+    /// in the `answers.toml` next to the day's input
+    Verify {
+        /// Same as for `run`
+        #[arg(default_value = "all")]
+        days: DaySelection,
+    },
+
+    /// Runs the given days under dhat, printing a table of the heap usage of
+    /// parsing and of every part. Needs `--features dhat-heap`.
+    Heap {
+        /// Same as for `run`
+        #[arg(default_value = "all")]
+        days: DaySelection,
+    },
+
+    /// Generates a new day from the template and registers it with the
+    /// runner and the benchmarks. Needs cargo-generate.
+    New {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+}
+
+/// Runs every requested part, printing answers as they come.
+///
+/// Failures are reported and do not stop the other days from running.
+fn run(days: &[u8], part: Option<u8>, input: Option<PathBuf>) -> color_eyre::Result<()> {
+    if input.is_some() && days.len() != 1 {
+        bail!("--input can only be used when running a single day");
+    }
+
+    let total = Instant::now();
+    let mut failures = 0;
+
+    for day in days
+        .iter()
+        .map(|d| days::find(*d).expect("selection is valid"))
+    {
+        let path = input
+            .clone()
+            .unwrap_or_else(|| input::default_path(day.day));
+        let text = match input::read_file(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                println!("Day {:>2}: {:#}", day.day, eyre!(e));
+                failures += 1;
+                continue;
+            }
+        };
+
+        for p in [1, 2]
+            .into_iter()
+            .filter(|p| part.is_none_or(|only| only == *p))
+        {
+            let Some(solve) = day.part(p) else {
+                if part.is_some() {
+                    return Err(eyre!("Day {} has no part {}", day.day, p));
+                }
+                continue;
+            };
+
+            let start = Instant::now();
+            let answer = solve(&text);
+            let elapsed = start.elapsed();
+
+            match answer {
+                Ok(answer) => println!(
+                    "Day {:>2} Part {}: {} ({:.2?})",
+                    day.day, p, answer, elapsed
+                ),
+                Err(e) => {
+                    println!(
+                        "Day {:>2} Part {}: FAILED: {:#} ({:.2?})",
+                        day.day, p, e, elapsed
+                    );
+                    failures += 1;
+                }
+            }
+        }
+    }
+
+    if days.len() > 1 {
+        println!("Total: {:.2?}", total.elapsed());
+    }
+
+    if failures > 0 {
+        bail!("{} part(s) failed", failures);
+    }
+    Ok(())
+}
+
+/// Reads an input along with the answers confirmed for it.
+fn load_with_answers(path: &Path) -> color_eyre::Result<(String, Answers)> {
+    let text = input::read_file(path)?;
+    let answers = Answers::load_for(path)
+        .wrap_err_with(|| format!("Invalid {}", Answers::path_for(path).display()))?;
+    Ok((text, answers))
+}
+
+/// Runs every part of the given days, reporting PASS, FAIL or UNKNOWN
+/// for each of them.
+///
+/// Parts that fail to solve count as FAIL.
+fn verify(days: &[u8]) -> color_eyre::Result<()> {
+    let mut summary = Summary::default();
+
+    for day in days
+        .iter()
+        .map(|d| days::find(*d).expect("selection is valid"))
+    {
+        let path = input::default_path(day.day);
+        let (text, answers) = match load_with_answers(&path) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                println!("Day {:>2}: FAIL: {:#}", day.day, e);
+                summary.failed += 1;
+                continue;
+            }
+        };
+
+        for (p, solve) in [1, 2]
+            .into_iter()
+            .filter_map(|p| day.part(p).map(|s| (p, s)))
+        {
+            let start = Instant::now();
+            let answer = solve(&text);
+            let elapsed = start.elapsed();
+
+            let answer = match answer {
+                Ok(answer) => answer,
+                Err(e) => {
+                    println!(
+                        "Day {:>2} Part {}: FAIL: {:#} ({:.2?})",
+                        day.day, p, e, elapsed
+                    );
+                    summary.failed += 1;
+                    continue;
+                }
+            };
+
+            let verdict = Verdict::check(answers.part(p), &answer);
+            match &verdict {
+                Verdict::Pass => println!("Day {:>2} Part {}: PASS ({:.2?})", day.day, p, elapsed),
+                Verdict::Fail { expected } => println!(
+                    "Day {:>2} Part {}: FAIL: expected {:?}, got {:?} ({:.2?})",
+                    day.day, p, expected, answer, elapsed
+                ),
+                Verdict::Unknown => println!(
+                    "Day {:>2} Part {}: UNKNOWN: {} ({:.2?})",
+                    day.day, p, answer, elapsed
+                ),
+            }
+            summary.add(&verdict);
+        }
+    }
+
+    println!("Verified: {}", summary);
+
+    if summary.failed > 0 {
+        bail!("{} part(s) failed verification", summary.failed);
+    }
+    Ok(())
+}
+
+/// Profiles the heap usage of every stage of the given days.
+///
+/// Failures are reported and do not stop the other days from running.
+fn heap(days: &[u8]) -> color_eyre::Result<()> {
+    if !cfg!(feature = "dhat-heap") {
+        bail!("heap profiling needs the runner built with `--features dhat-heap`");
+    }
+
+    println!(
+        "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}",
+        "Day", "Stage", "Total", "Peak", "Allocations"
+    );
+
+    let mut failures = 0;
+    for day in days
+        .iter()
+        .map(|d| days::find(*d).expect("selection is valid"))
+    {
+        let mut profiler = HeapProfiler::default();
+        let result = input::read_file(&input::default_path(day.day))
+            .map_err(Into::into)
+            .and_then(|text| (day.stages)(&text, &mut profiler));
+
+        for (stage, usage) in profiler.stages {
+            println!(
+                "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}",
+                day.day,
+                stage,
+                human_bytes(usage.total_bytes),
+                human_bytes(usage.peak_bytes as u64),
+                usage.allocations
+            );
+        }
+
+        if let Err(e) = result {
+            println!("{:>3}  FAILED: {:#}", day.day, e);
+            failures += 1;
+        }
+    }
+
+    if failures > 0 {
+        bail!("{} day(s) failed", failures);
+    }
+    Ok(())
+}
+
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
+
+    let stdout_log = tracing_subscriber::fmt::layer().compact();
+
+    tracing_subscriber::registry()
+        .with(stdout_log)
+        .with(EnvFilter::from_default_env())
+        .init();
+
+    match Cli::parse().command {
+        Command::Run { days, part, input } => run(&days.0, part, input),
+        Command::Verify { days } => verify(&days.0),
+        Command::Heap { days } => heap(&days.0),
+        Command::New { day } => scaffold::new_day(day),
+    }
+}
@@ -0,0 +1,61 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use color_eyre::eyre::{bail, WrapErr};
+
+/// Tools that run every registered day.
+const RUNNERS: [&str; 2] = ["aoc", "aoc-bench"];
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+fn run(command: &mut Command) -> color_eyre::Result<()> {
+    let status = command
+        .status()
+        .wrap_err_with(|| format!("Cannot run {:?}", command.get_program()))?;
+    if !status.success() {
+        bail!("{:?} failed: {}", command, status);
+    }
+    Ok(())
+}
+
+/// Generates `aocN` from the `template/` directory with cargo-generate and
+/// registers it with the runner and the benchmarks.
+pub fn new_day(day: u8) -> color_eyre::Result<()> {
+    let root = workspace_root();
+    let name = format!("aoc{}", day);
+    let path = root.join(&name);
+
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+
+    run(Command::new("cargo")
+        .arg("generate")
+        .arg("--path")
+        .arg(root.join("template"))
+        .args(["--name", &name])
+        .args(["--define", &format!("day={}", day)])
+        .arg("--destination")
+        .arg(&root)
+        .args(["--vcs", "none"]))?;
+
+    for runner in RUNNERS {
+        run(Command::new("cargo")
+            .arg("add")
+            .arg("--manifest-path")
+            .arg(root.join(runner).join("Cargo.toml"))
+            .arg("--path")
+            .arg(&path))?;
+    }
+
+    println!(
+        "Generated {}, add its input as {}",
+        name,
+        path.join("input.txt").display()
+    );
+    Ok(())
+}
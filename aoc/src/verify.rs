@@ -0,0 +1,75 @@
+use std::fmt::Display;
+
+/// How an answer compares to the confirmed one.
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// Nothing confirmed yet for this part
+    Unknown,
+}
+
+impl Verdict {
+    pub fn check(expected: Option<&str>, actual: &str) -> Verdict {
+        match expected {
+            None => Verdict::Unknown,
+            Some(expected) if expected == actual => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+/// Counts of every verdict, with failures to solve counted as failed.
+#[derive(Debug, Default, PartialEq)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub unknown: usize,
+}
+
+impl Summary {
+    pub fn add(&mut self, verdict: &Verdict) {
+        match verdict {
+            Verdict::Pass => self.passed += 1,
+            Verdict::Fail { .. } => self.failed += 1,
+            Verdict::Unknown => self.unknown += 1,
+        }
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} unknown",
+            self.passed, self.failed, self.unknown
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verdicts() {
+        assert_eq!(Verdict::check(Some("42"), "42"), Verdict::Pass);
+        assert_eq!(
+            Verdict::check(Some("42"), "43"),
+            Verdict::Fail {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(Verdict::check(None, "42"), Verdict::Unknown);
+
+        let mut summary = Summary::default();
+        summary.add(&Verdict::Pass);
+        summary.add(&Verdict::Unknown);
+        summary.add(&Verdict::Pass);
+        assert_eq!(summary.to_string(), "2 passed, 0 failed, 1 unknown");
+    }
+}
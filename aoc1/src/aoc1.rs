@@ -1,24 +1,17 @@
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
-
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
 static ALLOC: dhat::Alloc = dhat::Alloc;
 
 #[tracing::instrument]
-fn main() {
+fn main() -> color_eyre::eyre::Result<()> {
     #[cfg(feature = "dhat-heap")]
     let _profiler = dhat::Profiler::new_heap();
 
-    let stdout_log = tracing_subscriber::fmt::layer().compact();
+    color_eyre::install()?;
 
-    tracing_subscriber::registry()
-        .with(stdout_log)
-        .with(EnvFilter::from_default_env())
-        .init();
+    aoc_support::binary::init_tracing();
 
-    let s1 = aoc1::part1(include_str!("../input.txt"));
-    println!("Part 1: {}", s1);
+    let input = aoc_support::input::load(1)?;
 
-    let s2 = aoc1::part2(include_str!("../input.txt"));
-    println!("Part 2: {}", s2);
+    aoc_support::binary::solve::<aoc1::Solver>(&input)
 }
@@ -1,7 +1,10 @@
 use std::{collections::HashMap, iter::zip};
 
+use aoc_support::Solution;
+use color_eyre::eyre::ensure;
+
 #[derive(Debug, PartialEq)]
-struct Input {
+pub struct Input {
     v1: Vec<u32>,
     v2: Vec<u32>,
 }
@@ -32,28 +35,50 @@ mod parse {
     }
 }
 
-pub fn part1(input: &str) -> u32 {
-    let (r, mut d) = parse::input(input).expect("Valid input");
-    assert_eq!(r, "");
+pub struct Solver;
 
-    d.v1.sort();
-    d.v2.sort();
+impl Solution for Solver {
+    const DAY: u8 = 1;
 
-    zip(d.v1, d.v2).fold(0u32, |v, p| v + p.1.abs_diff(p.0))
-}
+    type Input = Input;
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Params = ();
 
-pub fn part2(input: &str) -> u32 {
-    let (r, d) = parse::input(input).expect("Valid input");
-    assert_eq!(r, "");
+    fn parse(input: &str) -> color_eyre::Result<Input> {
+        let (r, d) = parse::input(input).map_err(|e| e.to_owned())?;
+        ensure!(r.is_empty(), "Unparsed data remained: {:?}", r);
+        Ok(d)
+    }
+
+    fn part1(d: &Input, _: &()) -> color_eyre::Result<u32> {
+        let mut v1 = d.v1.clone();
+        let mut v2 = d.v2.clone();
+        v1.sort();
+        v2.sort();
+
+        Ok(zip(v1, v2).fold(0u32, |v, p| v + p.1.abs_diff(p.0)))
+    }
+
+    fn part2(d: &Input, _: &()) -> color_eyre::Result<u32> {
+        // 2nd list has occurences
+        let freq_map = d.v2.iter().copied().fold(HashMap::new(), |mut map, value| {
+            map.entry(value).and_modify(|frq| *frq += 1).or_insert(1);
+            map
+        });
+
+        Ok(d.v1
+            .iter()
+            .fold(0u32, |s, v| s + v * freq_map.get(v).unwrap_or(&0u32)))
+    }
+}
 
-    // 2nd list has occurences
-    let freq_map = d.v2.iter().copied().fold(HashMap::new(), |mut map, value| {
-        map.entry(value).and_modify(|frq| *frq += 1).or_insert(1);
-        map
-    });
+pub fn part1(input: &str) -> u32 {
+    Solver::solve1(input, &()).expect("Valid input")
+}
 
-    d.v1.iter()
-        .fold(0u32, |s, v| s + v * freq_map.get(v).unwrap_or(&0u32))
+pub fn part2(input: &str) -> u32 {
+    Solver::solve2(input, &()).expect("Valid input")
 }
 
 #[cfg(test)]
@@ -78,13 +103,5 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(include_str!("../example.txt")), 11);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(include_str!("../example.txt")), 31);
-    }
+    aoc_support::example_tests!(Solver);
 }
@@ -1,5 +1,10 @@
+use std::sync::LazyLock;
+
 use divan::black_box;
 
+static INPUT: LazyLock<String> =
+    LazyLock::new(|| aoc_support::input::from_inputs_dir(10).expect("benchmark input"));
+
 fn main() {
     // Run registered benchmarks.
     divan::main();
@@ -7,10 +12,10 @@ fn main() {
 
 #[divan::bench]
 fn part1() {
-    aoc10::part1(black_box(include_str!("../input.txt")));
+    aoc10::part1(black_box(INPUT.as_str()));
 }
 
 #[divan::bench]
 fn part2() {
-    aoc10::part2(black_box(include_str!("../input.txt")));
+    aoc10::part2(black_box(INPUT.as_str()));
 }
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use aoc_support::Solution;
+use color_eyre::eyre::ensure;
 use nom::{
     character::complete::{self, line_ending, space0},
     multi::{many0, many1},
@@ -66,22 +68,41 @@ pub fn parse_input(s: &str) -> IResult<&str, Vec<usize>> {
         .parse(s)
 }
 
-pub fn part1(input: &str) -> usize {
-    let (r, v) = parse_input(input).expect("valid input");
-    assert!(r.is_empty());
+pub struct Solver;
 
-    let mut cache = BlinkCache::default();
+impl Solution for Solver {
+    const DAY: u8 = 11;
 
-    v.iter().map(|v| cache.multiply(*v, 25)).sum()
-}
+    type Input = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Params = ();
 
-pub fn part2(input: &str) -> usize {
-    let (r, v) = parse_input(input).expect("valid input");
-    assert!(r.is_empty());
+    fn parse(input: &str) -> color_eyre::Result<Vec<usize>> {
+        let (r, v) = parse_input(input).map_err(|e| e.to_owned())?;
+        ensure!(r.is_empty(), "Unparsed data remained: {:?}", r);
+        Ok(v)
+    }
 
-    let mut cache = BlinkCache::default();
+    fn part1(v: &Vec<usize>, _: &()) -> color_eyre::Result<usize> {
+        let mut cache = BlinkCache::default();
 
-    v.iter().map(|v| cache.multiply(*v, 75)).sum()
+        Ok(v.iter().map(|v| cache.multiply(*v, 25)).sum())
+    }
+
+    fn part2(v: &Vec<usize>, _: &()) -> color_eyre::Result<usize> {
+        let mut cache = BlinkCache::default();
+
+        Ok(v.iter().map(|v| cache.multiply(*v, 75)).sum())
+    }
+}
+
+pub fn part1(input: &str) -> usize {
+    Solver::solve1(input, &()).expect("valid input")
+}
+
+pub fn part2(input: &str) -> usize {
+    Solver::solve2(input, &()).expect("valid input")
 }
 
 #[cfg(test)]
@@ -103,14 +124,5 @@ mod tests {
         assert_eq!(cache.multiply(22, 1), 2);
     }
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(include_str!("../example.txt")), 55312);
-    }
-
-    #[test]
-    fn test_part2() {
-        // this was not given in the test, but we added it here based on passing test anyway
-        assert_eq!(part2(include_str!("../example.txt")), 65601038650482);
-    }
+    aoc_support::example_tests!(Solver);
 }
@@ -1,3 +1,5 @@
+use aoc_support::Solution;
+use color_eyre::eyre::ensure;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending},
@@ -8,14 +10,14 @@ use nom::{
 };
 use nom_supreme::ParserExt;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 struct Values {
     x: i64,
     y: i64,
 }
 
-#[derive(Debug)]
-struct ClawMachine {
+#[derive(Debug, Clone)]
+pub struct ClawMachine {
     a: Values,
     b: Values,
     prize: Values,
@@ -75,49 +77,60 @@ fn parse_machines(input: &str) -> IResult<&str, Vec<ClawMachine>> {
     separated_list1(line_ending, parse_claw_machine).parse(input)
 }
 
-pub fn part1(input: &str) -> i64 {
-    let (r, machines) = parse_machines(input).expect("valid input");
-    assert!(r.is_empty());
-
-    machines
-        .iter()
-        .map(|m| {
-            let (a, b) = m.compute_presses();
-            a * 3 + b
-        })
-        .sum()
-}
+pub struct Solver;
 
-pub fn part2(input: &str) -> i64 {
-    let (r, mut machines) = parse_machines(input).expect("valid input");
-    assert!(r.is_empty());
+impl Solution for Solver {
+    const DAY: u8 = 13;
+
+    type Input = Vec<ClawMachine>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+    type Params = ();
+
+    fn parse(input: &str) -> color_eyre::Result<Vec<ClawMachine>> {
+        let (r, machines) = parse_machines(input).map_err(|e| e.to_owned())?;
+        ensure!(r.is_empty(), "Unparsed data remained: {:?}", r);
+        Ok(machines)
+    }
+
+    fn part1(machines: &Vec<ClawMachine>, _: &()) -> color_eyre::Result<i64> {
+        Ok(machines
+            .iter()
+            .map(|m| {
+                let (a, b) = m.compute_presses();
+                a * 3 + b
+            })
+            .sum())
+    }
+
+    fn part2(machines: &Vec<ClawMachine>, _: &()) -> color_eyre::Result<i64> {
+        let mut machines = machines.clone();
+        for m in machines.iter_mut() {
+            m.prize.x += 10000000000000;
+            m.prize.y += 10000000000000;
+        }
 
-    for m in machines.iter_mut() {
-        m.prize.x += 10000000000000;
-        m.prize.y += 10000000000000;
+        Ok(machines
+            .iter()
+            .map(|m| {
+                let (a, b) = m.compute_presses();
+                a * 3 + b
+            })
+            .sum())
     }
+}
 
-    machines
-        .iter()
-        .map(|m| {
-            let (a, b) = m.compute_presses();
-            a * 3 + b
-        })
-        .sum()
+pub fn part1(input: &str) -> i64 {
+    Solver::solve1(input, &()).expect("valid input")
+}
+
+pub fn part2(input: &str) -> i64 {
+    Solver::solve2(input, &()).expect("valid input")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    #[tracing_test::traced_test]
-    fn test_part1() {
-        assert_eq!(part1(include_str!("../example.txt")), 480);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(include_str!("../example.txt")), 875318608908);
-    }
+    aoc_support::example_tests!(Solver);
 }
@@ -1,9 +1,12 @@
 use std::{
-    collections::{HashMap, HashSet, VecDeque},
+    collections::{HashMap, HashSet},
     hash::Hash,
 };
 
+use aoc_support::Solution;
+use color_eyre::eyre::{ensure, eyre};
 use glam::IVec2;
+use map_parse::{Connectivity, DenseTopology, Map, Region};
 use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending},
@@ -13,10 +16,11 @@ use nom::{
 };
 use nom_supreme::ParserExt;
 use rayon::prelude::*;
+use serde::Deserialize;
 use tracing::Level;
 
 #[derive(Debug, PartialEq)]
-struct Robot {
+pub struct Robot {
     position: IVec2,
     velocity: IVec2,
 }
@@ -45,8 +49,8 @@ fn parse_input(s: &str) -> IResult<&str, Vec<Robot>> {
 
 #[derive(Debug)]
 struct Grid {
-    x: usize,
-    y: usize,
+    /// Robots wrap around the edges of the floor
+    floor: Map<bool>,
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
@@ -58,14 +62,38 @@ enum Quadrant {
 }
 
 impl Grid {
+    fn new(x: usize, y: usize) -> Self {
+        Self {
+            floor: Map::filled(y, x, false).with_topology(DenseTopology::Wrapping),
+        }
+    }
+
+    fn x(&self) -> usize {
+        self.floor.col_count()
+    }
+
+    fn y(&self) -> usize {
+        self.floor.row_count()
+    }
+
     fn move_robot(&self, r: &Robot, steps: usize) -> IVec2 {
-        (r.position + r.velocity * (steps as i32))
-            .rem_euclid(IVec2::new(self.x as i32, self.y as i32))
+        self.floor
+            .normalize(r.position + r.velocity * (steps as i32))
+            .expect("the floor wraps around")
+    }
+
+    /// Floor with `true` wherever there is at least one robot
+    fn robots_map(&self, pos: &HashSet<IVec2>) -> Map<bool> {
+        let mut robots = self.floor.clone();
+        for p in pos {
+            *robots.get_mut(p).expect("the floor wraps around") = true;
+        }
+        robots
     }
 
     fn get_quadrant(&self, pos: IVec2) -> Option<Quadrant> {
-        let midx = (self.x / 2) as i32;
-        let midy = (self.y / 2) as i32;
+        let midx = (self.x() / 2) as i32;
+        let midy = (self.y() / 2) as i32;
 
         if (pos.x == midx) || (pos.y == midy) {
             return None;
@@ -81,44 +109,14 @@ impl Grid {
 
     #[allow(dead_code)]
     fn display_robots(&self, v: &HashSet<IVec2>, steps: usize) {
-        let mut s = String::with_capacity((self.x + 1) * self.y);
-        for y in 0..self.y {
-            for x in 0..self.x {
-                if v.contains(&IVec2::new(x as i32, y as i32)) {
-                    s.push('*');
-                } else {
-                    s.push('.');
-                }
-            }
-            s.push('\n');
-        }
+        let s = self
+            .robots_map(v)
+            .render_with(|_, robot| if *robot { '*' } else { '.' });
         tracing::info!("\n{}", s);
         tracing::info!("STEPS: {}", steps);
     }
 }
 
-pub fn part1(input: &str) -> usize {
-    let (r, robots) = parse_input(input).expect("valid input");
-    assert!(r.is_empty());
-
-    let g = Grid { x: 101, y: 103 };
-
-    let mut m = HashMap::new();
-
-    for q in robots
-        .iter()
-        .map(|r| g.move_robot(r, 100))
-        .map(|p| g.get_quadrant(p))
-    {
-        m.entry(q).and_modify(|v| *v += 1).or_insert(1_usize);
-    }
-
-    m.get(&Some(Quadrant::NW)).copied().unwrap_or(0)
-        * m.get(&Some(Quadrant::NE)).copied().unwrap_or(0_usize)
-        * m.get(&Some(Quadrant::SW)).copied().unwrap_or(0_usize)
-        * m.get(&Some(Quadrant::SE)).copied().unwrap_or(0_usize)
-}
-
 // How does a christmas tree look like:
 //     *
 //    ***
@@ -133,8 +131,8 @@ pub fn part1(input: &str) -> usize {
 // tree)
 #[allow(dead_code)]
 fn is_suspicious_shape1(g: &Grid, pos: &HashSet<IVec2>) -> bool {
-    for y in 0..(g.y as i32) {
-        let cnt = (0..(g.x as i32))
+    for y in 0..(g.y() as i32) {
+        let cnt = (0..(g.x() as i32))
             .map(|x| (x - 1, x))
             .map(|(x1, x2)| {
                 (
@@ -152,55 +150,31 @@ fn is_suspicious_shape1(g: &Grid, pos: &HashSet<IVec2>) -> bool {
     true
 }
 
+/// Regions of connected robots
+fn robot_regions(g: &Grid, pos: &HashSet<IVec2>, connectivity: Connectivity) -> Vec<Region> {
+    let robots = g.robots_map(pos);
+    robots
+        .regions(connectivity, |a, b| a == b)
+        .iter()
+        .filter(|r| robots.get(&r.cells[0]) == Some(&true))
+        .cloned()
+        .collect()
+}
+
 /// let all the robots BE CONNECTED (N/E/S/W)
 /// DOES NOT WORK (only some of the robots are building the tree)
 #[allow(dead_code)]
-fn is_suspicious_shape2(_: &Grid, pos: &HashSet<IVec2>) -> bool {
-    let mut to_check = VecDeque::new();
-    to_check.push_back(*pos.iter().next().expect("non-empty set"));
-
-    let mut connected = HashSet::new();
-
-    while let Some(value) = to_check.pop_front() {
-        connected.insert(value);
-        for x in -1..=1 {
-            for y in -1..=1 {
-                let other = value + IVec2::new(x, y);
-                if !connected.contains(&other) && pos.contains(&other) {
-                    to_check.push_back(other);
-                }
-            }
-        }
-    }
-
-    connected.len() == pos.len()
+fn is_suspicious_shape2(g: &Grid, pos: &HashSet<IVec2>) -> bool {
+    robot_regions(g, pos, Connectivity::Eight).len() == 1
 }
 
 // this WORKS: at 7138 ...
 #[allow(dead_code)]
-fn is_suspicious_shape3(_: &Grid, pos: &HashSet<IVec2>) -> bool {
+fn is_suspicious_shape3(g: &Grid, pos: &HashSet<IVec2>) -> bool {
     // find the largest connected line and filter based on that ...
-    let mut connected = HashSet::new();
-    while &connected != pos {
-        let mut to_check = VecDeque::new();
-        to_check.push_back(*pos.difference(&connected).next().expect("non-empty set"));
-
-        let mut cnt = 1;
-        while let Some(value) = to_check.pop_front() {
-            connected.insert(value);
-            cnt += 1;
-            for (x, y) in [(-1, 0), (0, -1), (1, 0), (0, 1)] {
-                let other = value + IVec2::new(x, y);
-                if !connected.contains(&other) && pos.contains(&other) {
-                    to_check.push_back(other);
-                }
-            }
-        }
-        if cnt > 60 {
-            return true;
-        }
-    }
-    false
+    robot_regions(g, pos, Connectivity::Four)
+        .iter()
+        .any(|r| r.area() >= 60)
 }
 
 #[allow(dead_code)]
@@ -211,37 +185,91 @@ fn is_suspicious_shape(_: &Grid, pos: &Vec<IVec2>) -> bool {
     pos.len() == pos.iter().collect::<HashSet<_>>().len()
 }
 
-pub fn part2(input: &str) -> usize {
-    let (r, robots) = parse_input(input).expect("valid input");
-    assert!(r.is_empty());
+/// Size of the floor the robots move on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct Params {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            width: 101,
+            height: 103,
+        }
+    }
+}
 
-    let g = Grid { x: 101, y: 103 };
+pub struct Solver;
 
-    let steps = (0..(g.x * g.y))
-        .into_par_iter()
-        .find_first(|sc| {
-            let pos = robots.iter().map(|r| g.move_robot(r, *sc)).collect();
-            is_suspicious_shape(&g, &pos)
-        })
-        .expect("has something");
+impl Solution for Solver {
+    const DAY: u8 = 14;
 
-    if tracing::enabled!(Level::INFO) {
-        g.display_robots(
-            &robots.iter().map(|r| g.move_robot(r, steps)).collect(),
-            steps,
-        );
+    type Input = Vec<Robot>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Params = Params;
+
+    fn parse(input: &str) -> color_eyre::Result<Vec<Robot>> {
+        let (r, robots) = parse_input(input).map_err(|e| e.to_owned())?;
+        ensure!(r.is_empty(), "Unparsed data remained: {:?}", r);
+        Ok(robots)
+    }
+
+    fn part1(robots: &Vec<Robot>, params: &Params) -> color_eyre::Result<usize> {
+        let g = Grid::new(params.width, params.height);
+        let mut m = HashMap::new();
+
+        for q in robots
+            .iter()
+            .map(|r| g.move_robot(r, 100))
+            .map(|p| g.get_quadrant(p))
+        {
+            m.entry(q).and_modify(|v| *v += 1).or_insert(1_usize);
+        }
+
+        Ok(m.get(&Some(Quadrant::NW)).copied().unwrap_or(0)
+            * m.get(&Some(Quadrant::NE)).copied().unwrap_or(0_usize)
+            * m.get(&Some(Quadrant::SW)).copied().unwrap_or(0_usize)
+            * m.get(&Some(Quadrant::SE)).copied().unwrap_or(0_usize))
+    }
+
+    fn part2(robots: &Vec<Robot>, params: &Params) -> color_eyre::Result<usize> {
+        let g = Grid::new(params.width, params.height);
+        let steps = (0..(g.x() * g.y()))
+            .into_par_iter()
+            .find_first(|sc| {
+                let pos = robots.iter().map(|r| g.move_robot(r, *sc)).collect();
+                is_suspicious_shape(&g, &pos)
+            })
+            .ok_or_else(|| eyre!("no christmas tree found"))?;
+
+        if tracing::enabled!(Level::INFO) {
+            g.display_robots(
+                &robots.iter().map(|r| g.move_robot(r, steps)).collect(),
+                steps,
+            );
+        }
+        Ok(steps)
     }
-    steps
+}
+
+pub fn part1(input: &str) -> usize {
+    Solver::solve1(input, &Params::default()).expect("valid input")
+}
+
+pub fn part2(input: &str) -> usize {
+    Solver::solve2(input, &Params::default()).expect("valid input")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test_log::test;
 
-    #[test]
+    #[test_log::test]
     fn test_move() {
-        let g = Grid { x: 11, y: 7 };
+        let g = Grid::new(11, 7);
         assert_eq!(
             g.move_robot(
                 &Robot {
@@ -264,4 +292,6 @@ mod tests {
             IVec2::new(6, 5)
         );
     }
+
+    aoc_support::example_tests!(Solver);
 }
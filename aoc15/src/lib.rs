@@ -1,10 +1,11 @@
-use std::{
-    collections::HashMap,
-    fmt::{Display, Write},
-};
+use std::fmt::Display;
 
+use aoc_support::{
+    error::{complete, ProcessingError},
+    Solution,
+};
 use glam::IVec2;
-use map_parse::Map;
+use map_parse::{Map, Markers};
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -17,29 +18,24 @@ use nom_supreme::ParserExt;
 
 #[derive(thiserror::Error, Debug, PartialEq)]
 enum InputParseError {
-    #[error("Failed to parse using Nom")]
-    NomError(#[source] nom::Err<nom::error::Error<String>>),
+    #[error(transparent)]
+    Parse(#[from] ProcessingError),
 
-    #[error("Unparsed data remained: {0:?}")]
-    UnparsedData(String),
+    #[error(transparent)]
+    InvalidMap(#[from] map_parse::MapParseError),
 }
 
-impl<INNER: Into<String>> From<nom::Err<nom::error::Error<INNER>>> for InputParseError {
-    fn from(value: nom::Err<nom::error::Error<INNER>>) -> Self {
-        InputParseError::NomError(value.map_input(|i| i.into()))
+map_parse::glyph_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Cell {
+        Wall = '#',
+        Box = 'O',
+        Empty = '.',
+        LargeBoxLeft = '[',
+        LargeBoxRight = ']',
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Cell {
-    Wall,
-    Robot,
-    Box,
-    Empty,
-    LargeBoxLeft,
-    LargeBoxRight,
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Instruction {
     Left,
@@ -59,21 +55,8 @@ impl Instruction {
     }
 }
 
-impl map_parse::Parseable for Cell {
-    type Item = Cell;
-
-    fn parse(s: &str) -> nom::IResult<&str, Self::Item> {
-        alt((
-            tag("#").value(Cell::Wall),
-            tag("@").value(Cell::Robot),
-            tag("O").value(Cell::Box),
-            tag(".").value(Cell::Empty),
-        ))
-        .parse(s)
-    }
-}
-
-struct Input {
+#[derive(Clone)]
+pub struct Input {
     map: Map<Cell>,
     instructions: Vec<Instruction>,
     robot_position: IVec2,
@@ -81,25 +64,7 @@ struct Input {
 
 impl Display for Input {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.map.row_count() {
-            for x in 0..self.map.col_count() {
-                let pos = IVec2::new(x as i32, y as i32);
-                if self.robot_position == pos {
-                    f.write_char('@')?;
-                } else {
-                    match self.map.get(&pos).expect("valid location") {
-                        Cell::Wall => f.write_char('#'),
-                        Cell::Empty => f.write_char('.'),
-                        Cell::Box => f.write_char('O'),
-                        Cell::Robot => f.write_char('@'),
-                        Cell::LargeBoxLeft => f.write_char('['),
-                        Cell::LargeBoxRight => f.write_char(']'),
-                    }?;
-                }
-            }
-            f.write_char('\n')?;
-        }
-        Ok(())
+        f.write_str(&self.map.render_overlays(&[&('@', self.robot_position)]))
     }
 }
 
@@ -150,7 +115,6 @@ impl Input {
     fn can_push(&self, pos: IVec2, direction: IVec2) -> bool {
         match self.map.get(&pos).expect("valid position") {
             Cell::Wall => false,
-            Cell::Robot => panic!("Robot should not exist in the map directly"),
             Cell::Box => self.can_push(pos + direction, direction),
             Cell::Empty => true,
             Cell::LargeBoxLeft => {
@@ -186,38 +150,13 @@ impl Input {
     }
 
     fn double_horizontally(self) -> Self {
-        let rows = self.map.row_count();
-        let cols = self.map.col_count() * 2;
-
-        let mut expanded_map = HashMap::new();
-
-        // compute expansion
-        for (pos, value) in self.map.values_iter() {
-            let p1 = *pos * IVec2::new(2, 1);
-            let p2 = p1 + IVec2::new(1, 0);
-            match value {
-                Cell::Wall => {
-                    expanded_map.insert(p1, Cell::Wall);
-                    expanded_map.insert(p2, Cell::Wall);
-                }
-                Cell::Robot => {
-                    expanded_map.insert(p1, Cell::Robot);
-                    expanded_map.insert(p2, Cell::Empty);
-                }
-                Cell::Box => {
-                    expanded_map.insert(p1, Cell::LargeBoxLeft);
-                    expanded_map.insert(p2, Cell::LargeBoxRight);
-                }
-                Cell::Empty => {
-                    expanded_map.insert(p1, Cell::Empty);
-                    expanded_map.insert(p2, Cell::Empty);
-                }
-                _ => panic!("Map cannot be doubled - it looks already doubled."),
-            };
-        }
-
         Input {
-            map: Map::create(rows, cols, expanded_map),
+            map: self.map.expand(|value| match value {
+                Cell::Wall => [Cell::Wall, Cell::Wall],
+                Cell::Box => [Cell::LargeBoxLeft, Cell::LargeBoxRight],
+                Cell::Empty => [Cell::Empty, Cell::Empty],
+                _ => panic!("Map cannot be doubled - it looks already doubled."),
+            }),
             instructions: self.instructions,
             robot_position: self.robot_position * IVec2::new(2, 1),
         }
@@ -225,101 +164,88 @@ impl Input {
 }
 
 fn parse_input(s: &str) -> Result<Input, InputParseError> {
-    let (rest, mut map) = Map::<Cell>::parse(s).map_err(InputParseError::from)?;
-
-    let (rest, instructions) = many1(
-        alt((
-            tag("^").value(Instruction::Up),
-            tag("<").value(Instruction::Left),
-            tag(">").value(Instruction::Right),
-            tag("v").value(Instruction::Down),
-        ))
-        .terminated(opt(line_ending)),
-    )
-    .preceded_by(many0(line_ending))
-    .terminated(many0(line_ending))
-    .parse(rest)
-    .map_err(InputParseError::from)?;
-
-    if !rest.is_empty() {
-        return Err(InputParseError::UnparsedData(rest.into()));
-    }
-
-    let mut robot_position = None;
-    if let Some((pos, _)) = map.values_iter().find(|(_, value)| **value == Cell::Robot) {
-        robot_position = Some(*pos);
-    }
-
-    if let Some(pos) = robot_position {
-        *map.get_mut(&pos).expect("Pos is valid") = Cell::Empty;
-    }
+    let (rest, marked) =
+        Map::<Cell>::try_parse_with_markers(s, &Markers::new(Cell::Empty).required('@'))?;
+
+    let instructions = complete(
+        s,
+        many1(
+            alt((
+                tag("^").value(Instruction::Up),
+                tag("<").value(Instruction::Left),
+                tag(">").value(Instruction::Right),
+                tag("v").value(Instruction::Down),
+            ))
+            .terminated(opt(line_ending)),
+        )
+        .preceded_by(many0(line_ending))
+        .terminated(many0(line_ending))
+        .parse(rest),
+    )?;
 
     Ok(Input {
-        map,
+        robot_position: marked.marker('@').expect("required marker"),
+        map: marked.map,
         instructions,
-        robot_position: robot_position.expect("A robot position must exist"),
     })
 }
 
-pub fn part1(s: &str) -> color_eyre::Result<i32> {
-    let mut input = parse_input(s)?;
+pub struct Solver;
 
-    for instruction in input.instructions.clone() {
-        input.perform(instruction);
+impl Solution for Solver {
+    const DAY: u8 = 15;
+
+    type Input = Input;
+    type Answer1 = i32;
+    type Answer2 = i32;
+    type Params = ();
+
+    fn parse(s: &str) -> color_eyre::Result<Input> {
+        Ok(parse_input(s)?)
     }
 
-    Ok(input
-        .map
-        .values_iter()
-        .filter(|(_, value)| **value == Cell::Box)
-        .map(|(p, _)| p.y * 100 + p.x)
-        .sum())
-}
+    fn part1(input: &Input, _: &()) -> color_eyre::Result<i32> {
+        let mut input = input.clone();
 
-pub fn part2(s: &str) -> color_eyre::Result<i32> {
-    let mut input = parse_input(s)?;
+        for instruction in input.instructions.clone() {
+            input.perform(instruction);
+        }
+
+        Ok(input
+            .map
+            .values_iter()
+            .filter(|(_, value)| **value == Cell::Box)
+            .map(|(p, _)| p.y * 100 + p.x)
+            .sum())
+    }
+
+    fn part2(input: &Input, _: &()) -> color_eyre::Result<i32> {
+        let mut input = input.clone().double_horizontally();
 
-    input = input.double_horizontally();
+        for instruction in input.instructions.clone() {
+            input.perform(instruction);
+        }
 
-    for instruction in input.instructions.clone() {
-        input.perform(instruction);
+        Ok(input
+            .map
+            .values_iter()
+            .filter(|(_, value)| **value == Cell::LargeBoxLeft)
+            .map(|(p, _)| p.y * 100 + p.x)
+            .sum())
     }
+}
+
+pub fn part1(s: &str) -> color_eyre::Result<i32> {
+    Solver::solve1(s, &())
+}
 
-    Ok(input
-        .map
-        .values_iter()
-        .filter(|(_, value)| **value == Cell::LargeBoxLeft)
-        .map(|(p, _)| p.y * 100 + p.x)
-        .sum())
+pub fn part2(s: &str) -> color_eyre::Result<i32> {
+    Solver::solve2(s, &())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Once;
 
-    static INIT: Once = Once::new();
-    pub fn init_tests() {
-        INIT.call_once(|| {
-            color_eyre::install().unwrap_or(());
-        });
-    }
-
-    #[test]
-    fn test_part1() {
-        init_tests();
-        assert_eq!(
-            part1(include_str!("../example.txt")).expect("success"),
-            2028
-        );
-    }
-
-    #[test]
-    fn test_part2() {
-        init_tests();
-        assert_eq!(
-            part2(include_str!("../example.txt")).expect("success"),
-            1751
-        );
-    }
+    aoc_support::example_tests!(Solver);
 }
@@ -1,5 +1,10 @@
+use std::sync::LazyLock;
+
 use divan::black_box;
 
+static INPUT: LazyLock<String> =
+    LazyLock::new(|| aoc_support::input::from_inputs_dir(17).expect("benchmark input"));
+
 fn main() {
     // Run registered benchmarks.
     divan::main();
@@ -7,10 +12,30 @@ fn main() {
 
 #[divan::bench]
 fn part1() {
-    aoc17::part1(black_box(include_str!("../input.txt"))).unwrap();
+    aoc17::part1(black_box(INPUT.as_str())).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    aoc17::part2(black_box(include_str!("../input.txt"))).unwrap();
+    aoc17::part2(black_box(INPUT.as_str())).unwrap();
+}
+
+/// Checks 2^16 values of A against the program itself, as the fallback
+/// search of part 2 does.
+#[divan::bench]
+fn compiled_batch(bencher: divan::Bencher) {
+    use aoc_support::Solution;
+
+    let program = aoc17::Solver::parse(&INPUT).unwrap();
+    let compiled = program.compile();
+    let target = INPUT
+        .trim()
+        .rsplit(' ')
+        .next()
+        .unwrap()
+        .split(',')
+        .map(|v| v.parse().unwrap())
+        .collect::<Vec<u8>>();
+
+    bencher.bench_local(|| compiled.find_first(black_box(0..1u128 << 16), &target));
 }
@@ -0,0 +1,304 @@
+//! Finding the value of A that makes a program output itself (part 2).
+//!
+//! Puzzle programs are a single loop: every iteration outputs one value,
+//! shifts A right by a constant and jumps back while A is not 0. B and C are
+//! written before they are read, so nothing but A carries over to the next
+//! iteration, and everything output after the first iteration depends only
+//! on A shifted once. Then A can be built a few bits at a time from the last
+//! output backwards. The output may depend on any bits of A, not just the
+//! lowest, so every candidate is confirmed by running the program.
+//!
+//! [Program::shape] checks that a program really has that shape; for
+//! anything else [solve] falls back to trying values of A in order.
+
+use rayon::prelude::*;
+
+use crate::{
+    compiled::{BudgetExhausted, Compiled},
+    debugger::Register,
+    ComboOperand, Instruction, LiteralOperand, Program,
+};
+
+/// How far the fallback search goes by default.
+pub const DEFAULT_SEARCH_LIMIT: u128 = 1 << 24;
+
+/// The shape that allows solving a program backwards.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Shape {
+    /// Bits A is shifted right by in every iteration
+    pub shift: u32,
+}
+
+/// Why a program does not have the [Shape] of puzzle programs.
+#[derive(thiserror::Error, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    #[error("the program does not end with `jnz 0`")]
+    NoLoop,
+
+    #[error("instruction {0} jumps, only the last one may")]
+    InnerJump(usize),
+
+    #[error("A is shifted {0} times per iteration instead of once")]
+    ShiftCount(usize),
+
+    #[error("A is shifted by register {0:?} instead of a constant")]
+    VariableShift(Register),
+
+    #[error("A is shifted by 0, so the loop only ends if A starts at 0")]
+    ZeroShift,
+
+    #[error("{0} values are output per iteration instead of 1")]
+    OutputCount(usize),
+
+    #[error("{0:?} is read before it is written, so it carries state between iterations")]
+    CarriedState(Register),
+}
+
+/// Why [solve] found no A.
+#[derive(thiserror::Error, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NoSolution {
+    #[error("no value of A makes the program output its last {0} values")]
+    DeadEnd(usize),
+
+    #[error("A would need {0} bits to output every value, more than fit in a u128")]
+    TooWide(u32),
+
+    #[error("{reason}, and no A up to {limit} makes it output itself")]
+    NotFound { reason: Rejection, limit: u128 },
+
+    #[error("no A makes the program output itself, and some did not halt: {0}")]
+    OutOfBudget(#[from] BudgetExhausted),
+}
+
+impl ComboOperand {
+    fn register(&self) -> Option<Register> {
+        match self {
+            ComboOperand::Register(n) => Some(Register::ALL[*n as usize]),
+            ComboOperand::Value(_) => None,
+        }
+    }
+}
+
+/// Checks that `instructions` are a loop with the [Shape] of puzzle
+/// programs.
+fn analyze(instructions: &[Instruction]) -> Result<Shape, Rejection> {
+    let Some((Instruction::JumpIfNotZero(LiteralOperand::Value(0)), body)) =
+        instructions.split_last()
+    else {
+        return Err(Rejection::NoLoop);
+    };
+
+    let mut shifts = Vec::new();
+    let mut outputs = 0;
+    // B and C, once written in the current iteration
+    let mut written = [false; 3];
+
+    for (pc, instruction) in body.iter().enumerate() {
+        let (reads, writes) = match instruction {
+            Instruction::DivisionA(operand) => {
+                shifts.push(*operand);
+                (operand.register(), None)
+            }
+            Instruction::BitwiseXorB(_) => (Some(Register::B), Some(Register::B)),
+            Instruction::Modulo8(operand) => (operand.register(), Some(Register::B)),
+            Instruction::JumpIfNotZero(_) => return Err(Rejection::InnerJump(pc)),
+            Instruction::BitwiseXorC => {
+                if !written[Register::C as usize] {
+                    return Err(Rejection::CarriedState(Register::C));
+                }
+                (Some(Register::B), Some(Register::B))
+            }
+            Instruction::Out(operand) => {
+                outputs += 1;
+                (operand.register(), None)
+            }
+            Instruction::DivisionB(operand) => (operand.register(), Some(Register::B)),
+            Instruction::DivisionC(operand) => (operand.register(), Some(Register::C)),
+        };
+
+        if let Some(register) = reads.filter(|r| *r != Register::A) {
+            if !written[register as usize] {
+                return Err(Rejection::CarriedState(register));
+            }
+        }
+        if let Some(register) = writes {
+            written[register as usize] = true;
+        }
+    }
+
+    if outputs != 1 {
+        return Err(Rejection::OutputCount(outputs));
+    }
+
+    match shifts.as_slice() {
+        [ComboOperand::Value(0)] => Err(Rejection::ZeroShift),
+        [ComboOperand::Value(shift)] => Ok(Shape {
+            shift: *shift as u32,
+        }),
+        [ComboOperand::Register(n)] => Err(Rejection::VariableShift(Register::ALL[*n as usize])),
+        _ => Err(Rejection::ShiftCount(shifts.len())),
+    }
+}
+
+impl Program {
+    /// The [Shape] of the program, or why it does not have it.
+    pub fn shape(&self) -> Result<Shape, Rejection> {
+        analyze(&self.instructions)
+    }
+}
+
+/// The smallest A that makes the program output itself.
+///
+/// Programs with the [Shape] of puzzle programs are solved directly,
+/// others by trying every A up to `limit`. Every run of the program may
+/// take `budget` instructions (see [crate::compiled::DEFAULT_STEP_BUDGET]),
+/// runs that take longer count as not outputting the program.
+pub fn solve(program: &Program, limit: u128, budget: usize) -> Result<u128, NoSolution> {
+    let target = &program.raw_program;
+    let compiled = program.compile().with_budget(budget);
+
+    match program.shape() {
+        Ok(shape) => solve_backwards(&compiled, target, shape),
+        Err(reason) => compiled
+            .find_first(0..=limit, target)?
+            .ok_or(NoSolution::NotFound { reason, limit }),
+    }
+}
+
+/// Every iteration consumes `shape.shift` bits of A, the last iteration
+/// the highest ones. So the A values that output the last `n` values are
+/// the ones for the last `n - 1`, followed by any `shift` bits.
+#[tracing::instrument(skip(compiled, target))]
+fn solve_backwards(compiled: &Compiled, target: &[u8], shape: Shape) -> Result<u128, NoSolution> {
+    // the loop ends once A is 0, so A has at most this many bits
+    let bits = u32::try_from(target.len())
+        .ok()
+        .and_then(|len| len.checked_mul(shape.shift))
+        .unwrap_or(u32::MAX);
+    if bits > u128::BITS {
+        return Err(NoSolution::TooWide(bits));
+    }
+
+    let mut candidates = vec![0];
+
+    for digits in 1..=target.len() {
+        let tail = &target[target.len() - digits..];
+        candidates = compiled.find_all(
+            candidates.par_iter().flat_map_iter(|prefix| {
+                (0..1 << shape.shift).map(move |bits| (prefix << shape.shift) | bits)
+            }),
+            tail,
+        )?;
+
+        tracing::debug!(
+            "{} candidates for the last {} values",
+            candidates.len(),
+            digits
+        );
+        if candidates.is_empty() {
+            return Err(NoSolution::DeadEnd(digits));
+        }
+    }
+
+    Ok(*candidates.iter().min().expect("not empty"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{asm::assemble, compiled::DEFAULT_STEP_BUDGET};
+
+    use super::*;
+
+    fn program(source: &str) -> Program {
+        Program::new([0, 0, 0], assemble(source).expect("valid source")).expect("valid program")
+    }
+
+    #[test]
+    fn shapes() {
+        assert_eq!(
+            program("adv 3\nout A\njnz 0").shape(),
+            Ok(Shape { shift: 3 })
+        );
+        assert_eq!(
+            program("bst A\nbxl 5\ncdv B\nbxl 6\nbxc\nout B\nadv 2\njnz 0").shape(),
+            Ok(Shape { shift: 2 })
+        );
+
+        assert_eq!(program("adv 3\nout A").shape(), Err(Rejection::NoLoop));
+        assert_eq!(
+            program("jnz 0\nout A\njnz 0").shape(),
+            Err(Rejection::InnerJump(0))
+        );
+        assert_eq!(
+            program("adv 1\nadv 2\nout A\njnz 0").shape(),
+            Err(Rejection::ShiftCount(2))
+        );
+        assert_eq!(
+            program("bst A\nadv B\nout A\njnz 0").shape(),
+            Err(Rejection::VariableShift(Register::B))
+        );
+        assert_eq!(
+            program("adv 0\nout A\njnz 0").shape(),
+            Err(Rejection::ZeroShift)
+        );
+        assert_eq!(
+            program("adv 3\nout A\nout A\njnz 0").shape(),
+            Err(Rejection::OutputCount(2))
+        );
+        assert_eq!(
+            program("bxl 1\nout B\nadv 3\njnz 0").shape(),
+            Err(Rejection::CarriedState(Register::B))
+        );
+        assert_eq!(
+            program("bst A\nbxc\nout B\nadv 3\njnz 0").shape(),
+            Err(Rejection::CarriedState(Register::C))
+        );
+    }
+
+    #[test]
+    fn solutions() {
+        let example = program("adv 3\nout A\njnz 0");
+        assert_eq!(solve(&example, 0, DEFAULT_STEP_BUDGET), Ok(117440));
+
+        // the same answer, the slow way
+        assert_eq!(
+            example
+                .compile()
+                .find_first(0..=117440u128, &example.raw_program),
+            Ok(Some(117440))
+        );
+    }
+
+    #[test]
+    fn no_solutions() {
+        assert_eq!(
+            solve(&program("adv 3\nout 1\njnz 0"), 0, DEFAULT_STEP_BUDGET),
+            Err(NoSolution::DeadEnd(1))
+        );
+        assert_eq!(
+            solve(
+                &program("bxl 1\nout B\nadv 3\njnz 0"),
+                1000,
+                DEFAULT_STEP_BUDGET
+            ),
+            Err(NoSolution::NotFound {
+                reason: Rejection::CarriedState(Register::B),
+                limit: 1000
+            })
+        );
+        assert_eq!(
+            solve(&program("l: jnz l"), 10, 100),
+            Err(NoSolution::OutOfBudget(BudgetExhausted(100)))
+        );
+
+        // 44 values, 3 bits of A each
+        let wide = program(&format!(
+            "bst A\nout B\n{}adv 3\njnz 0",
+            "bxl 0\n".repeat(18)
+        ));
+        assert_eq!(
+            solve(&wide, 0, DEFAULT_STEP_BUDGET),
+            Err(NoSolution::TooWide(44 * 3))
+        );
+    }
+}
@@ -0,0 +1,437 @@
+//! A text assembly format for the three-bit computer:
+//!
+//! ```text
+//! ; prints A in octal, lowest digit first
+//! loop:
+//!     out A
+//!     adv 3
+//!     jnz loop
+//! ```
+//!
+//! Mnemonics are the ones of the puzzle. Combo operands are `0` to `3` or a
+//! register `A`, `B` or `C`, literal operands are `0` to `7`. Jumps must go
+//! to the start of an instruction, an even address, and `jnz` also takes a
+//! label, which stands for the byte address of the instruction after it. `bxc` ignores its operand, so it can be left out. Comments start with
+//! `;`.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{ComboOperand, Instruction};
+
+/// Mnemonics, indexed by opcode
+const MNEMONICS: [&str; 8] = ["adv", "bxl", "bst", "jnz", "bxc", "out", "bdv", "cdv"];
+
+const JNZ: u8 = 3;
+const BXC: u8 = 4;
+
+/// Highest value of a 3-bit operand, also the furthest jump
+const MAX_OPERAND: u8 = 7;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum AsmError {
+    #[error("Line {line}: unknown instruction {mnemonic:?}")]
+    UnknownMnemonic { line: usize, mnemonic: String },
+
+    #[error("Line {line}: {mnemonic} needs an operand")]
+    MissingOperand { line: usize, mnemonic: &'static str },
+
+    #[error("Line {line}: {operand:?} is not a valid operand for {mnemonic}")]
+    InvalidOperand {
+        line: usize,
+        mnemonic: &'static str,
+        operand: String,
+    },
+
+    #[error("Line {line}: unexpected {text:?} after the operand")]
+    TrailingText { line: usize, text: String },
+
+    #[error("Line {line}: {label:?} is not a valid label name")]
+    InvalidLabel { line: usize, label: String },
+
+    #[error("Line {line}: label {label:?} is already defined")]
+    DuplicateLabel { line: usize, label: String },
+
+    #[error("Line {line}: label {label:?} is not defined")]
+    UnknownLabel { line: usize, label: String },
+
+    #[error("Line {line}: label {label:?} is at address {address}, past the last jump target {MAX_OPERAND}")]
+    JumpTooFar {
+        line: usize,
+        label: String,
+        address: usize,
+    },
+
+    #[error("Jump at address {address} goes to odd address {target}, which is not the start of an instruction")]
+    OddJumpTarget { address: usize, target: u8 },
+
+    #[error("Program has an odd number of bytes ({0})")]
+    OddLength(usize),
+
+    #[error("Invalid instruction {opcode},{operand} at address {address}")]
+    InvalidInstruction {
+        address: usize,
+        opcode: u8,
+        operand: u8,
+    },
+}
+
+/// An instruction with its operand still as text.
+struct Statement<'a> {
+    line: usize,
+    opcode: u8,
+    operand: Option<&'a str>,
+}
+
+fn is_label(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Whether the operand of `opcode` is a combo operand.
+fn has_combo(opcode: u8) -> bool {
+    matches!(opcode, 0 | 2 | 5 | 6 | 7)
+}
+
+fn literal(operand: &str) -> Option<u8> {
+    operand.parse().ok().filter(|v| *v <= MAX_OPERAND)
+}
+
+fn combo(operand: &str) -> Option<u8> {
+    match operand {
+        "A" => Some(4),
+        "B" => Some(5),
+        "C" => Some(6),
+        _ => operand.parse().ok().filter(|v| *v <= 3),
+    }
+}
+
+impl Instruction {
+    /// The instruction as a line of assembly, with jump targets as numbers.
+    pub(crate) fn to_asm(self) -> String {
+        match self {
+            Instruction::DivisionA(operand) => format!("adv {}", operand),
+            Instruction::BitwiseXorB(operand) => format!("bxl {}", operand),
+            Instruction::Modulo8(operand) => format!("bst {}", operand),
+            Instruction::JumpIfNotZero(operand) => format!("jnz {}", operand),
+            Instruction::BitwiseXorC => "bxc".into(),
+            Instruction::Out(operand) => format!("out {}", operand),
+            Instruction::DivisionB(operand) => format!("bdv {}", operand),
+            Instruction::DivisionC(operand) => format!("cdv {}", operand),
+        }
+    }
+}
+
+/// Assembles source text into the raw program, as it appears after
+/// `Program:` in the puzzle input.
+pub fn assemble(source: &str) -> Result<Vec<u8>, AsmError> {
+    let mut statements = Vec::new();
+    let mut labels = HashMap::new();
+
+    for (idx, text) in source.lines().enumerate() {
+        let line = idx + 1;
+        let mut text = text.split(';').next().unwrap_or_default().trim();
+
+        if let Some((label, rest)) = text.split_once(':') {
+            let label = label.trim();
+            if !is_label(label) {
+                return Err(AsmError::InvalidLabel {
+                    line,
+                    label: label.into(),
+                });
+            }
+            if labels.insert(label, statements.len() * 2).is_some() {
+                return Err(AsmError::DuplicateLabel {
+                    line,
+                    label: label.into(),
+                });
+            }
+            text = rest.trim();
+        }
+
+        let mut words = text.split_whitespace();
+        let Some(mnemonic) = words.next() else {
+            continue;
+        };
+        let opcode = MNEMONICS
+            .iter()
+            .position(|m| *m == mnemonic)
+            .ok_or_else(|| AsmError::UnknownMnemonic {
+                line,
+                mnemonic: mnemonic.into(),
+            })? as u8;
+        let operand = words.next();
+        if let Some(extra) = words.next() {
+            return Err(AsmError::TrailingText {
+                line,
+                text: extra.into(),
+            });
+        }
+
+        statements.push(Statement {
+            line,
+            opcode,
+            operand,
+        });
+    }
+
+    let mut program = Vec::with_capacity(statements.len() * 2);
+    for (
+        idx,
+        Statement {
+            line,
+            opcode,
+            operand,
+        },
+    ) in statements.into_iter().enumerate()
+    {
+        let mnemonic = MNEMONICS[opcode as usize];
+        let invalid = |operand: &str| AsmError::InvalidOperand {
+            line,
+            mnemonic,
+            operand: operand.into(),
+        };
+
+        let value = match (opcode, operand) {
+            (BXC, None) => 0,
+            (_, None) => return Err(AsmError::MissingOperand { line, mnemonic }),
+            (JNZ, Some(operand)) if is_label(operand) => {
+                let address = *labels.get(operand).ok_or_else(|| AsmError::UnknownLabel {
+                    line,
+                    label: operand.into(),
+                })?;
+                if address > MAX_OPERAND as usize {
+                    return Err(AsmError::JumpTooFar {
+                        line,
+                        label: operand.into(),
+                        address,
+                    });
+                }
+                address as u8
+            }
+            (_, Some(operand)) if has_combo(opcode) => {
+                combo(operand).ok_or_else(|| invalid(operand))?
+            }
+            (_, Some(operand)) => literal(operand).ok_or_else(|| invalid(operand))?,
+        };
+        if opcode == JNZ && !value.is_multiple_of(2) {
+            return Err(AsmError::OddJumpTarget {
+                address: idx * 2,
+                target: value,
+            });
+        }
+
+        program.push(opcode);
+        program.push(value);
+    }
+
+    Ok(program)
+}
+
+/// Turns a raw program back into source text that [assemble]s to the same
+/// bytes. Jump targets inside the program get labels.
+pub fn disassemble(program: &[u8]) -> Result<String, AsmError> {
+    if !program.len().is_multiple_of(2) {
+        return Err(AsmError::OddLength(program.len()));
+    }
+
+    let targets = program
+        .chunks_exact(2)
+        .filter(|c| c[0] == JNZ && (c[1] as usize) < program.len())
+        .map(|c| c[1])
+        .collect::<HashSet<_>>();
+
+    let mut source = String::new();
+    for (idx, chunk) in program.chunks_exact(2).enumerate() {
+        let (opcode, operand) = (chunk[0], chunk[1]);
+        let address = idx * 2;
+        let invalid = || AsmError::InvalidInstruction {
+            address,
+            opcode,
+            operand,
+        };
+
+        if targets.contains(&(address as u8)) {
+            source.push_str(&format!("l{}:\n", address));
+        }
+
+        let mnemonic = MNEMONICS.get(opcode as usize).ok_or_else(invalid)?;
+        let operand = match opcode {
+            BXC if operand == 0 => None,
+            JNZ if !operand.is_multiple_of(2) => {
+                return Err(AsmError::OddJumpTarget {
+                    address,
+                    target: operand,
+                })
+            }
+            JNZ if targets.contains(&operand) => Some(format!("l{}", operand)),
+            _ if has_combo(opcode) => Some(
+                ComboOperand::try_from(operand)
+                    .map_err(|_| invalid())?
+                    .to_string(),
+            ),
+            _ if operand <= MAX_OPERAND => Some(operand.to_string()),
+            _ => return Err(invalid()),
+        };
+
+        match operand {
+            Some(operand) => source.push_str(&format!("    {} {}\n", mnemonic, operand)),
+            None => source.push_str(&format!("    {}\n", mnemonic)),
+        }
+    }
+
+    Ok(source)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Program;
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let source = "\
+            ; prints A in octal, lowest digit first\n\
+            start: adv 1 ; dropped right away\n\
+            l2:\n\
+                out A\n\
+                adv 3\n\
+                jnz l2\n\
+                bxc\n";
+        let program = assemble(source).expect("valid source");
+        assert_eq!(program, vec![0, 1, 5, 4, 0, 3, 3, 2, 4, 0]);
+
+        let text = disassemble(&program).expect("valid program");
+        assert_eq!(
+            text,
+            "    adv 1\nl2:\n    out A\n    adv 3\n    jnz l2\n    bxc\n"
+        );
+        assert_eq!(assemble(&text), Ok(program));
+
+        // raw programs that do not come from an assembler
+        for program in [vec![0, 1, 5, 4, 3, 0], vec![3, 6, 4, 7, 1, 7, 2, 6]] {
+            let text = disassemble(&program).expect("valid program");
+            assert_eq!(assemble(&text), Ok(program));
+        }
+    }
+
+    #[test]
+    fn odd_jumps() {
+        // even jumps past the program keep their number
+        let text = disassemble(&[0, 1, 3, 6]).expect("valid program");
+        assert_eq!(text, "    adv 1\n    jnz 6\n");
+        assert_eq!(assemble(&text), Ok(vec![0, 1, 3, 6]));
+
+        let odd = || AsmError::OddJumpTarget {
+            address: 2,
+            target: 3,
+        };
+        assert_eq!(assemble("adv 1\njnz 3"), Err(odd()));
+        assert_eq!(disassemble(&[0, 1, 3, 3]), Err(odd()));
+    }
+
+    #[test]
+    fn run_assembled() {
+        let program = assemble(
+            "
+                bxl 1
+            loop:
+                out A
+                adv 1
+                jnz loop
+            ",
+        )
+        .expect("valid source");
+
+        let mut program = Program::new([10, 0, 0], program).expect("valid program");
+        assert_eq!(program.run(), vec![2, 5, 2, 1]);
+        assert_eq!(program.registers.values, [0, 1, 0]);
+    }
+
+    #[test]
+    fn assembly_errors() {
+        assert_eq!(
+            assemble("adv 1\nmul 3"),
+            Err(AsmError::UnknownMnemonic {
+                line: 2,
+                mnemonic: "mul".into()
+            })
+        );
+        assert_eq!(
+            assemble("out"),
+            Err(AsmError::MissingOperand {
+                line: 1,
+                mnemonic: "out"
+            })
+        );
+        assert_eq!(
+            assemble("bst 5"),
+            Err(AsmError::InvalidOperand {
+                line: 1,
+                mnemonic: "bst",
+                operand: "5".into()
+            })
+        );
+        assert_eq!(
+            assemble("bxl A"),
+            Err(AsmError::InvalidOperand {
+                line: 1,
+                mnemonic: "bxl",
+                operand: "A".into()
+            })
+        );
+        assert_eq!(
+            assemble("jnz nowhere"),
+            Err(AsmError::UnknownLabel {
+                line: 1,
+                label: "nowhere".into()
+            })
+        );
+        assert_eq!(
+            assemble("a: out A\na: out B"),
+            Err(AsmError::DuplicateLabel {
+                line: 2,
+                label: "a".into()
+            })
+        );
+        assert_eq!(
+            assemble("out A\nout A\nout A\nout A\nfar: out B\njnz far"),
+            Err(AsmError::JumpTooFar {
+                line: 6,
+                label: "far".into(),
+                address: 8
+            })
+        );
+        assert_eq!(
+            assemble("out A B"),
+            Err(AsmError::TrailingText {
+                line: 1,
+                text: "B".into()
+            })
+        );
+    }
+
+    #[test]
+    fn disassembly_errors() {
+        assert_eq!(disassemble(&[0, 1, 5]), Err(AsmError::OddLength(3)));
+        assert_eq!(
+            disassemble(&[0, 1, 5, 7]),
+            Err(AsmError::InvalidInstruction {
+                address: 2,
+                opcode: 5,
+                operand: 7
+            })
+        );
+        assert_eq!(
+            disassemble(&[8, 0]),
+            Err(AsmError::InvalidInstruction {
+                address: 0,
+                opcode: 8,
+                operand: 0
+            })
+        );
+    }
+}
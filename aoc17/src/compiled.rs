@@ -0,0 +1,358 @@
+//! Programs compiled for running many times with different values of A.
+//!
+//! Compiling resolves operands once: constant combo operands become
+//! immediates, divisions become shifts and jump targets become instruction
+//! indexes. Runs then only need the three registers, so they do not
+//! allocate and can be spread over threads with rayon.
+//!
+//! Programs may loop forever, so every run is limited to a budget of
+//! instructions, [DEFAULT_STEP_BUDGET] unless set with
+//! [Compiled::with_budget].
+
+use std::{
+    ops::ControlFlow,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use rayon::prelude::*;
+
+use crate::{shift, ComboOperand, Instruction, LiteralOperand, Program};
+
+/// Instructions a single run may take by default before the program is
+/// considered to loop forever. Puzzle programs halt after a few hundred.
+pub const DEFAULT_STEP_BUDGET: usize = 10_000;
+
+/// A run took more instructions than the budget of the [Compiled] program.
+#[derive(thiserror::Error, Debug, Copy, Clone, PartialEq, Eq)]
+#[error("the program did not halt within {0} instructions")]
+pub struct BudgetExhausted(pub usize);
+
+/// How a single run ended.
+enum Ending {
+    Halted,
+    /// `emit` broke
+    Stopped,
+    BudgetExhausted,
+}
+
+/// A single compiled instruction. Registers are indexes into `[A, B, C]`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Op {
+    /// `dst = A >> by`
+    Shift { dst: usize, by: u32 },
+    /// `dst = A >> src`
+    ShiftBy { dst: usize, src: usize },
+    /// `B ^= value`
+    XorB(u128),
+    /// `B ^= C`
+    XorC,
+    /// `B = value`
+    SetB(u128),
+    /// `B = src % 8`
+    Mod8(usize),
+    /// Outputs `value`
+    Out(u8),
+    /// Outputs `src % 8`
+    OutRegister(usize),
+    /// Jumps to instruction `target` if A is not 0
+    Jnz(usize),
+}
+
+fn division(dst: usize, operand: ComboOperand) -> Op {
+    match operand {
+        ComboOperand::Value(by) => Op::Shift { dst, by: by as u32 },
+        ComboOperand::Register(src) => Op::ShiftBy {
+            dst,
+            src: src as usize,
+        },
+    }
+}
+
+impl From<Instruction> for Op {
+    fn from(instruction: Instruction) -> Self {
+        match instruction {
+            Instruction::DivisionA(operand) => division(0, operand),
+            Instruction::BitwiseXorB(operand) => Op::XorB(operand.value()),
+            Instruction::Modulo8(ComboOperand::Value(v)) => Op::SetB(v as u128 % 8),
+            Instruction::Modulo8(ComboOperand::Register(src)) => Op::Mod8(src as usize),
+            Instruction::JumpIfNotZero(LiteralOperand::Value(target)) => {
+                // operands are byte addresses, ops are instructions.
+                // Programs reject odd addresses when decoding.
+                Op::Jnz(target as usize / 2)
+            }
+            Instruction::BitwiseXorC => Op::XorC,
+            Instruction::Out(ComboOperand::Value(v)) => Op::Out(v % 8),
+            Instruction::Out(ComboOperand::Register(src)) => Op::OutRegister(src as usize),
+            Instruction::DivisionB(operand) => division(1, operand),
+            Instruction::DivisionC(operand) => division(2, operand),
+        }
+    }
+}
+
+/// A [Program] ready to run for any value of A. B and C start with the
+/// values of the program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compiled {
+    ops: Vec<Op>,
+    b: u128,
+    c: u128,
+    budget: usize,
+}
+
+impl Compiled {
+    /// Limits every run to `steps` instructions.
+    pub fn with_budget(mut self, steps: usize) -> Self {
+        self.budget = steps;
+        self
+    }
+
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Runs with `a` in register A, passing every output to `emit` until
+    /// it breaks.
+    fn execute(&self, a: u128, mut emit: impl FnMut(u8) -> ControlFlow<()>) -> Ending {
+        let mut registers = [a, self.b, self.c];
+        let mut pc = 0;
+
+        for _ in 0..self.budget {
+            let Some(op) = self.ops.get(pc) else {
+                return Ending::Halted;
+            };
+            pc += 1;
+
+            let output = match *op {
+                Op::Shift { dst, by } => {
+                    registers[dst] = registers[0] >> by;
+                    continue;
+                }
+                Op::ShiftBy { dst, src } => {
+                    registers[dst] = shift(registers[0], registers[src]);
+                    continue;
+                }
+                Op::XorB(value) => {
+                    registers[1] ^= value;
+                    continue;
+                }
+                Op::XorC => {
+                    registers[1] ^= registers[2];
+                    continue;
+                }
+                Op::SetB(value) => {
+                    registers[1] = value;
+                    continue;
+                }
+                Op::Mod8(src) => {
+                    registers[1] = registers[src] % 8;
+                    continue;
+                }
+                Op::Jnz(target) => {
+                    if registers[0] != 0 {
+                        pc = target;
+                    }
+                    continue;
+                }
+                Op::Out(value) => value,
+                Op::OutRegister(src) => (registers[src] % 8) as u8,
+            };
+
+            if emit(output).is_break() {
+                return Ending::Stopped;
+            }
+        }
+
+        if pc < self.ops.len() {
+            Ending::BudgetExhausted
+        } else {
+            Ending::Halted
+        }
+    }
+
+    /// Outputs of running with `a` in register A until the program halts.
+    pub fn run(&self, a: u128) -> Result<Vec<u8>, BudgetExhausted> {
+        let mut output = Vec::new();
+        match self.execute(a, |value| {
+            output.push(value);
+            ControlFlow::Continue(())
+        }) {
+            Ending::BudgetExhausted => Err(BudgetExhausted(self.budget)),
+            _ => Ok(output),
+        }
+    }
+
+    /// Whether the program outputs exactly `target` when starting with `a`
+    /// in register A.
+    ///
+    /// Gives up as soon as the output differs. Fails if the budget runs
+    /// out while the output still matches.
+    pub fn outputs(&self, a: u128, target: &[u8]) -> Result<bool, BudgetExhausted> {
+        let mut produced = 0;
+        let ending = self.execute(a, |value| {
+            if target.get(produced) != Some(&value) {
+                return ControlFlow::Break(());
+            }
+            produced += 1;
+            ControlFlow::Continue(())
+        });
+        match ending {
+            Ending::Halted => Ok(produced == target.len()),
+            Ending::Stopped => Ok(false),
+            Ending::BudgetExhausted => Err(BudgetExhausted(self.budget)),
+        }
+    }
+
+    /// [outputs](Self::outputs), counting a run out of budget as not
+    /// matching but recording it in `exhausted`.
+    fn matches(&self, a: u128, target: &[u8], exhausted: &AtomicBool) -> bool {
+        self.outputs(a, target).unwrap_or_else(|_| {
+            exhausted.store(true, Ordering::Relaxed);
+            false
+        })
+    }
+
+    /// The first of `candidates` for A that [outputs](Self::outputs)
+    /// `target`, checking them in parallel.
+    ///
+    /// Candidates that run out of budget do not match. If none matches
+    /// and any ran out, the search fails instead of returning `None`.
+    pub fn find_first(
+        &self,
+        candidates: impl IntoParallelIterator<Item = u128>,
+        target: &[u8],
+    ) -> Result<Option<u128>, BudgetExhausted> {
+        let exhausted = AtomicBool::new(false);
+        let found = candidates
+            .into_par_iter()
+            .find_first(|a| self.matches(*a, target, &exhausted));
+        match found {
+            None if exhausted.into_inner() => Err(BudgetExhausted(self.budget)),
+            found => Ok(found),
+        }
+    }
+
+    /// All of `candidates` for A that [output](Self::outputs) `target`, in
+    /// order, checking them in parallel.
+    ///
+    /// Fails like [find_first](Self::find_first) when none matches.
+    pub fn find_all(
+        &self,
+        candidates: impl IntoParallelIterator<Item = u128>,
+        target: &[u8],
+    ) -> Result<Vec<u128>, BudgetExhausted> {
+        let exhausted = AtomicBool::new(false);
+        let found = candidates
+            .into_par_iter()
+            .filter(|a| self.matches(*a, target, &exhausted))
+            .collect::<Vec<_>>();
+        if found.is_empty() && exhausted.into_inner() {
+            return Err(BudgetExhausted(self.budget));
+        }
+        Ok(found)
+    }
+}
+
+impl Program {
+    /// Compiles the instructions, keeping the current B and C registers.
+    /// Runs have the [DEFAULT_STEP_BUDGET].
+    pub fn compile(&self) -> Compiled {
+        Compiled {
+            ops: self.instructions.iter().map(|i| Op::from(*i)).collect(),
+            b: self.registers.values[1],
+            c: self.registers.values[2],
+            budget: DEFAULT_STEP_BUDGET,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::asm::assemble;
+
+    use super::*;
+
+    fn program(registers: [u128; 3], source: &str) -> Program {
+        Program::new(registers, assemble(source).expect("valid source")).expect("valid program")
+    }
+
+    #[test]
+    fn same_as_interpreter() {
+        for (registers, source) in [
+            ([729, 0, 0], "l: adv 1\nout A\njnz l"),
+            ([2024, 0, 0], "l: adv 1\nout A\njnz l"),
+            ([10, 0, 0], "bxl 1\nl: out A\nadv 1\njnz l"),
+            (
+                [2024, 0, 0],
+                "l: bst A\nbxl 5\ncdv B\nbxl 6\nbxc\nout B\nadv 3\njnz l",
+            ),
+            (
+                [12345, 3, 9],
+                "bdv 2\ncdv C\nbst 3\nout B\nout C\nout 2\nbxc\nout B",
+            ),
+        ] {
+            let mut interpreted = program(registers, source);
+            let compiled = interpreted.compile();
+            let expected = interpreted
+                .run()
+                .iter()
+                .map(|v| *v as u8)
+                .collect::<Vec<_>>();
+            assert_eq!(compiled.run(registers[0]), Ok(expected), "{}", source);
+        }
+    }
+
+    #[test]
+    fn huge_shifts() {
+        let compiled = program([0, 200, 0], "adv B\nout A").compile();
+        assert_eq!(compiled.run(u128::MAX), Ok(vec![0]));
+    }
+
+    #[test]
+    fn batches() {
+        let compiled = program([0, 0, 0], "l: adv 3\nout A\njnz l").compile();
+        let target = [0, 3, 5, 4, 3, 0];
+
+        assert_eq!(compiled.outputs(117440, &target), Ok(true));
+        assert_eq!(compiled.outputs(117440, &target[1..]), Ok(false));
+        assert_eq!(
+            compiled.find_first(0..=1_000_000u128, &target),
+            Ok(Some(117440))
+        );
+        assert_eq!(
+            compiled.find_all(0..1u128 << 12, &[1, 2, 3, 0]),
+            Ok(vec![
+                0o3210, 0o3211, 0o3212, 0o3213, 0o3214, 0o3215, 0o3216, 0o3217
+            ])
+        );
+        assert_eq!(compiled.find_first(0..8u128, &[7]), Ok(None));
+    }
+
+    #[test]
+    fn budgets() {
+        let endless = program([0, 0, 0], "l: jnz l").compile().with_budget(100);
+        assert_eq!(endless.run(1), Err(BudgetExhausted(100)));
+        assert_eq!(endless.run(0), Ok(vec![]));
+        assert_eq!(endless.outputs(1, &[]), Err(BudgetExhausted(100)));
+        assert_eq!(endless.find_first(0..100u128, &[]), Ok(Some(0)));
+        assert_eq!(
+            endless.find_first(1..100u128, &[]),
+            Err(BudgetExhausted(100))
+        );
+        assert_eq!(endless.find_all(1..100u128, &[]), Err(BudgetExhausted(100)));
+
+        // outputs 8 values in 24 instructions
+        let compiled = program([0, 0, 0], "l: adv 3\nout A\njnz l").compile();
+        assert_eq!(
+            compiled
+                .clone()
+                .with_budget(24)
+                .run(1 << 21)
+                .map(|o| o.len()),
+            Ok(8)
+        );
+        assert_eq!(
+            compiled.with_budget(23).run(1 << 21),
+            Err(BudgetExhausted(23))
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use std::{collections::HashSet, io::Write};
+
+use serde::Serialize;
+
+use crate::Program;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Register {
+    A,
+    B,
+    C,
+}
+
+impl Register {
+    pub(crate) const ALL: [Register; 3] = [Register::A, Register::B, Register::C];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Why [Debugger::run] returned.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Stop {
+    /// The pc left the program
+    Halted,
+    /// The pc reached a breakpoint. The instruction there did not run yet.
+    Breakpoint(usize),
+    /// A watched register changed value
+    Watchpoint {
+        register: Register,
+        old: u128,
+        new: u128,
+    },
+    /// The given number of instructions ran without halting
+    BudgetExhausted(usize),
+}
+
+/// One executed instruction, with the registers after it ran.
+///
+/// `pc` counts instructions, so it is half the byte address of jumps and
+/// of [crate::asm] labels.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceEntry {
+    pub step: usize,
+    pub pc: usize,
+    pub instruction: String,
+    pub registers: [u128; 3],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<u128>,
+}
+
+/// Runs a [Program] one instruction at a time, stopping on breakpoints,
+/// watched registers or after a budget of instructions.
+#[derive(Debug, Clone)]
+pub struct Debugger {
+    program: Program,
+    breakpoints: HashSet<usize>,
+    watchpoints: HashSet<Register>,
+    budget: Option<usize>,
+    steps: usize,
+    output: Vec<u128>,
+    trace: Option<Vec<TraceEntry>>,
+    /// The breakpoint the last [run](Self::run) stopped at
+    stopped_at: Option<usize>,
+}
+
+impl Debugger {
+    pub fn new(program: Program) -> Self {
+        Self {
+            program,
+            breakpoints: HashSet::new(),
+            watchpoints: HashSet::new(),
+            budget: None,
+            steps: 0,
+            output: Vec::new(),
+            trace: None,
+            stopped_at: None,
+        }
+    }
+
+    /// Stops [run](Self::run) when the pc reaches `pc`.
+    pub fn break_at(mut self, pc: usize) -> Self {
+        self.breakpoints.insert(pc);
+        self
+    }
+
+    /// Stops [run](Self::run) when `register` changes.
+    pub fn watch(mut self, register: Register) -> Self {
+        self.watchpoints.insert(register);
+        self
+    }
+
+    /// Stops [run](Self::run) once `steps` instructions ran in total, for
+    /// programs that may never halt.
+    pub fn with_budget(mut self, steps: usize) -> Self {
+        self.budget = Some(steps);
+        self
+    }
+
+    /// Keeps a [TraceEntry] of every instruction that runs, see
+    /// [write_trace](Self::write_trace).
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(Vec::new());
+        self
+    }
+
+    pub fn pc(&self) -> usize {
+        self.program.registers.pc
+    }
+
+    pub fn register(&self, register: Register) -> u128 {
+        self.program.registers.values[register.index()]
+    }
+
+    /// Number of instructions that ran so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn output(&self) -> &[u128] {
+        &self.output
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.pc() >= self.program.instructions.len()
+    }
+
+    /// Runs the instruction at the pc, unless the program halted.
+    pub fn step(&mut self) -> Option<TraceEntry> {
+        let pc = self.pc();
+        let instruction = *self.program.instructions.get(pc)?;
+
+        let output = self.program.registers.perform(instruction);
+        self.output.extend(output);
+
+        let entry = TraceEntry {
+            step: self.steps,
+            pc,
+            instruction: instruction.to_asm(),
+            registers: self.program.registers.values,
+            output,
+        };
+        self.steps += 1;
+
+        if let Some(trace) = self.trace.as_mut() {
+            trace.push(entry.clone());
+        }
+        Some(entry)
+    }
+
+    /// Runs instructions until the program halts or something makes it stop.
+    ///
+    /// Breakpoints are checked before each instruction, including the
+    /// first, except for the one the previous call stopped at, so calling
+    /// it again after a breakpoint continues past it.
+    pub fn run(&mut self) -> Stop {
+        let mut resumed_at = self.stopped_at.take();
+        loop {
+            let pc = self.pc();
+            if resumed_at.take() != Some(pc) && self.breakpoints.contains(&pc) {
+                self.stopped_at = Some(pc);
+                return Stop::Breakpoint(pc);
+            }
+
+            if self.budget.is_some_and(|budget| self.steps >= budget) {
+                return Stop::BudgetExhausted(self.steps);
+            }
+
+            let before = self.program.registers.values;
+            if self.step().is_none() {
+                return Stop::Halted;
+            }
+
+            let after = self.program.registers.values;
+            if let Some(register) = Register::ALL
+                .into_iter()
+                .find(|r| self.watchpoints.contains(r) && before[r.index()] != after[r.index()])
+            {
+                return Stop::Watchpoint {
+                    register,
+                    old: before[register.index()],
+                    new: after[register.index()],
+                };
+            }
+        }
+    }
+
+    /// The recorded trace, empty unless [with_trace](Self::with_trace) was
+    /// used.
+    pub fn trace(&self) -> &[TraceEntry] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    /// Writes the recorded trace as JSON lines, one per instruction.
+    pub fn write_trace(&self, out: &mut impl Write) -> std::io::Result<()> {
+        for entry in self.trace() {
+            serde_json::to_writer(&mut *out, entry)?;
+            out.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+}
+
+impl Program {
+    /// A debugger starting at the current state of the program.
+    pub fn debugger(&self) -> Debugger {
+        Debugger::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::asm::assemble;
+
+    use super::*;
+
+    fn program(registers: [u128; 3], source: &str) -> Program {
+        Program::new(registers, assemble(source).expect("valid source")).expect("valid program")
+    }
+
+    #[test]
+    fn stepping() {
+        let mut debugger = program([729, 0, 0], "l: adv 1\nout A\njnz l").debugger();
+
+        assert_eq!(
+            debugger.step(),
+            Some(TraceEntry {
+                step: 0,
+                pc: 0,
+                instruction: "adv 1".into(),
+                registers: [364, 0, 0],
+                output: None,
+            })
+        );
+        assert_eq!(debugger.step().and_then(|e| e.output), Some(4));
+        assert_eq!(debugger.pc(), 2);
+
+        assert_eq!(debugger.run(), Stop::Halted);
+        assert_eq!(debugger.output(), [4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+        assert!(debugger.is_halted());
+        assert_eq!(debugger.step(), None);
+    }
+
+    #[test]
+    fn breakpoints_and_watchpoints() {
+        let mut debugger = program([10, 0, 0], "bxl 1\nl: out A\nadv 1\njnz l")
+            .debugger()
+            .break_at(2)
+            .watch(Register::B);
+
+        assert_eq!(
+            debugger.run(),
+            Stop::Watchpoint {
+                register: Register::B,
+                old: 0,
+                new: 1
+            }
+        );
+        assert_eq!(debugger.run(), Stop::Breakpoint(2));
+        assert_eq!(debugger.register(Register::A), 10);
+        assert_eq!(debugger.output(), [2]);
+
+        // continues past the breakpoint it stopped at
+        assert_eq!(debugger.run(), Stop::Breakpoint(2));
+        assert_eq!(debugger.register(Register::A), 5);
+
+        let mut debugger = program([10, 0, 0], "bxl 1\nl: out A\nadv 1\njnz l").debugger();
+        assert_eq!(debugger.run(), Stop::Halted);
+        assert_eq!(debugger.output(), [2, 5, 2, 1]);
+    }
+
+    #[test]
+    fn breakpoint_at_start() {
+        let mut debugger = program([4, 0, 0], "out A\nadv 1\nout A")
+            .debugger()
+            .break_at(0);
+
+        assert_eq!(debugger.run(), Stop::Breakpoint(0));
+        assert_eq!(debugger.steps(), 0);
+        assert_eq!(debugger.run(), Stop::Halted);
+        assert_eq!(debugger.output(), [4, 2]);
+    }
+
+    #[test]
+    fn huge_shifts() {
+        let mut debugger = program([u128::MAX, 200, 0], "adv B\nout A").debugger();
+        assert_eq!(debugger.run(), Stop::Halted);
+        assert_eq!(debugger.output(), [0]);
+    }
+
+    #[test]
+    fn budget() {
+        let mut debugger = program([1, 0, 0], "l: jnz l").debugger().with_budget(100);
+        assert_eq!(debugger.run(), Stop::BudgetExhausted(100));
+        assert_eq!(debugger.run(), Stop::BudgetExhausted(100));
+        assert_eq!(debugger.steps(), 100);
+    }
+
+    #[test]
+    fn json_trace() {
+        let mut debugger = program([2, 0, 0], "out A\nbst A\nbxc")
+            .debugger()
+            .with_trace();
+        assert_eq!(debugger.run(), Stop::Halted);
+
+        let mut out = Vec::new();
+        debugger.write_trace(&mut out).expect("writes to memory");
+        assert_eq!(
+            String::from_utf8(out).expect("valid UTF-8"),
+            r#"{"step":0,"pc":0,"instruction":"out A","registers":[2,0,0],"output":2}
+{"step":1,"pc":1,"instruction":"bst A","registers":[2,2,0]}
+{"step":2,"pc":2,"instruction":"bxc","registers":[2,2,0]}
+"#
+        );
+    }
+}
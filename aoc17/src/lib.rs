@@ -1,21 +1,27 @@
 use std::fmt::Display;
 
+use aoc_support::{
+    error::{parse_complete, ProcessingError},
+    Solution,
+};
+use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending},
     multi::{many0, many1, separated_list1},
     sequence::tuple,
-    Parser as _,
 };
 use nom_supreme::ParserExt;
 
+pub mod analysis;
+pub mod asm;
+pub mod compiled;
+pub mod debugger;
+
 #[derive(thiserror::Error, Debug, PartialEq)]
 enum InputParseError {
-    #[error("Failed to parse using Nom")]
-    NomError(#[source] nom::Err<nom::error::Error<String>>),
-
-    #[error("Unparsed data remained: {0:?}")]
-    UnparsedData(String),
+    #[error(transparent)]
+    Parse(#[from] ProcessingError),
 
     #[error("Operand opcode {0:?} is not valid")]
     InvalidOperandOpcode(u8),
@@ -25,6 +31,9 @@ enum InputParseError {
 
     #[error("Failed to decode instructions: exactly 2 bytes needed")]
     InvalidDecodeLength,
+
+    #[error("Jump to odd address {0}, which is not the start of an instruction")]
+    OddJumpTarget(u8),
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -151,6 +160,7 @@ impl TryFrom<&[u8]> for Instruction {
             0 => Ok(Self::DivisionA(value[1].try_into()?)),
             1 => Ok(Self::BitwiseXorB(LiteralOperand::Value(value[1]))),
             2 => Ok(Self::Modulo8(value[1].try_into()?)),
+            3 if !value[1].is_multiple_of(2) => Err(InputParseError::OddJumpTarget(value[1])),
             3 => Ok(Self::JumpIfNotZero(LiteralOperand::Value(value[1]))),
             4 => Ok(Self::BitwiseXorC),
             5 => Ok(Self::Out(value[1].try_into()?)),
@@ -161,12 +171,20 @@ impl TryFrom<&[u8]> for Instruction {
     }
 }
 
+/// A / 2^by, which is 0 for shifts past the width of A.
+fn shift(a: u128, by: u128) -> u128 {
+    u32::try_from(by)
+        .ok()
+        .and_then(|by| a.checked_shr(by))
+        .unwrap_or(0)
+}
+
 impl Registers {
     // perform the operation and return an optional outpu
     fn perform(&mut self, instruction: Instruction) -> Option<u128> {
         match instruction {
             Instruction::DivisionA(combo_operand) => {
-                self.values[0] /= 1 << combo_operand.value(&self.values);
+                self.values[0] = shift(self.values[0], combo_operand.value(&self.values));
                 self.pc += 1;
             }
             Instruction::BitwiseXorB(literal_operand) => {
@@ -181,7 +199,9 @@ impl Registers {
                 if self.values[0] == 0 {
                     self.pc += 1;
                 } else {
-                    self.pc = literal_operand.value() as usize;
+                    // operands are byte addresses, pc counts instructions.
+                    // Decoding rejects odd addresses.
+                    self.pc = literal_operand.value() as usize / 2;
                 }
             }
             Instruction::BitwiseXorC => {
@@ -193,11 +213,11 @@ impl Registers {
                 return Some(combo_operand.value(&self.values) % 8);
             }
             Instruction::DivisionB(combo_operand) => {
-                self.values[1] = self.values[0] / (1 << combo_operand.value(&self.values));
+                self.values[1] = shift(self.values[0], combo_operand.value(&self.values));
                 self.pc += 1;
             }
             Instruction::DivisionC(combo_operand) => {
-                self.values[2] = self.values[0] / (1 << combo_operand.value(&self.values));
+                self.values[2] = shift(self.values[0], combo_operand.value(&self.values));
                 self.pc += 1;
             }
         }
@@ -207,13 +227,32 @@ impl Registers {
 }
 
 #[derive(Default, Debug, Clone)]
-struct Program {
+pub struct Program {
     registers: Registers,
     instructions: Vec<Instruction>,
     raw_program: Vec<u8>,
 }
 
 impl Program {
+    /// A program for the given A, B and C registers, decoded from its raw
+    /// bytes (see [asm::assemble]).
+    pub fn new(registers: [u128; 3], raw_program: Vec<u8>) -> color_eyre::Result<Self> {
+        Ok(Self::decode(registers, raw_program)?)
+    }
+
+    fn decode(registers: [u128; 3], raw_program: Vec<u8>) -> Result<Self, InputParseError> {
+        let instructions = raw_program
+            .chunks(2)
+            .map(Instruction::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            registers: Registers::new(registers, 0),
+            instructions,
+            raw_program,
+        })
+    }
+
     fn run(&mut self) -> Vec<u128> {
         let mut output_vec = Vec::new();
         while let Some(instruction) = self.instructions.get(self.registers.pc) {
@@ -226,101 +265,71 @@ impl Program {
 }
 
 fn parse_input(s: &str) -> Result<Program, InputParseError> {
-    let (rest, program) = tuple((
-        complete::u128
-            .preceded_by(tag("Register A: "))
-            .terminated(line_ending),
-        complete::u128
-            .preceded_by(tag("Register B: "))
-            .terminated(line_ending),
-        complete::u128
-            .preceded_by(tag("Register C: "))
-            .terminated(many1(line_ending)),
-        separated_list1(tag(","), complete::u8)
-            .preceded_by(tag("Program: "))
-            .terminated(many0(line_ending)),
-    ))
-    .map(|(a, b, c, raw_program)| Program {
-        registers: Registers::new([a, b, c], 0),
-        instructions: raw_program
-            .chunks_exact(2)
-            .map(|v| Instruction::try_from(v).expect("valid instruction"))
-            .collect(),
-        raw_program,
-    })
-    .parse(s)?;
-
-    if !rest.is_empty() {
-        return Err(InputParseError::UnparsedData(rest.into()));
-    }
-
-    Ok(program)
+    let (a, b, c, raw_program) = parse_complete(
+        tuple((
+            complete::u128
+                .preceded_by(tag("Register A: "))
+                .terminated(line_ending),
+            complete::u128
+                .preceded_by(tag("Register B: "))
+                .terminated(line_ending),
+            complete::u128
+                .preceded_by(tag("Register C: "))
+                .terminated(many1(line_ending)),
+            separated_list1(tag(","), complete::u8)
+                .preceded_by(tag("Program: "))
+                .terminated(many0(line_ending)),
+        )),
+        s,
+    )?;
+
+    Program::decode([a, b, c], raw_program)
 }
 
-impl<INNER: Into<String>> From<nom::Err<nom::error::Error<INNER>>> for InputParseError {
-    fn from(value: nom::Err<nom::error::Error<INNER>>) -> Self {
-        InputParseError::NomError(value.map_input(|i| i.into()))
-    }
-}
+pub struct Solver;
 
-pub fn part1(input: &str) -> color_eyre::Result<Vec<u128>> {
-    let mut program = parse_input(input)?;
+impl Solution for Solver {
+    const DAY: u8 = 17;
 
-    Ok(program.run())
-}
+    type Input = Program;
+    type Answer1 = String;
+    type Answer2 = u128;
+    type Params = ();
 
-#[tracing::instrument(ret)]
-pub fn find(start_program: &Program, target: &[u8]) -> Vec<u128> {
-    // find the output for the smaller chunks, then append a prefix
-    let (_, rest) = match target.split_first() {
-        Some((a, b)) => (a, b),
-        None => return vec![0], // Empty target
-    };
-
-    let mut choices = Vec::new();
-
-    for partial in find(start_program, rest) {
-        for suffix in 0..8 {
-            let choice = (partial << 3) | suffix;
+    fn parse(input: &str) -> color_eyre::Result<Program> {
+        Ok(parse_input(input)?)
+    }
 
-            let mut other = start_program.clone();
-            other.registers.values[0] = choice;
-            let result = other.run().iter().map(|v| *v as u8).collect::<Vec<_>>();
+    fn part1(program: &Program, _: &()) -> color_eyre::Result<String> {
+        Ok(program.clone().run().iter().join(","))
+    }
 
-            if result == target {
-                choices.push(choice);
-            }
+    fn part2(program: &Program, _: &()) -> color_eyre::Result<u128> {
+        tracing::info!("PROGRAM:");
+        for (idx, i) in program.instructions.iter().enumerate() {
+            tracing::info!("    {}: {:#}", idx, i);
         }
+
+        Ok(analysis::solve(
+            program,
+            analysis::DEFAULT_SEARCH_LIMIT,
+            compiled::DEFAULT_STEP_BUDGET,
+        )?)
     }
+}
 
-    choices
+/// Outputs of running the program, as individual values.
+pub fn part1(input: &str) -> color_eyre::Result<Vec<u128>> {
+    Ok(Solver::parse(input)?.run())
 }
 
 pub fn part2(input: &str) -> color_eyre::Result<u128> {
-    let program = parse_input(input)?;
-
-    tracing::info!("PROGRAM:");
-    for (idx, i) in program.instructions.iter().enumerate() {
-        tracing::info!("    {}: {:#}", idx, i);
-    }
-
-    Ok(*find(&program, &program.raw_program.to_vec())
-        .iter()
-        .min()
-        .expect("Has a solution"))
+    Solver::solve2(input, &())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Once;
-
-    static INIT: Once = Once::new();
-    pub fn init_tests() {
-        INIT.call_once(|| {
-            color_eyre::install().unwrap_or(());
-        });
-    }
 
     #[test]
     fn test_operations() {
@@ -392,20 +401,20 @@ mod tests {
     }
 
     #[test]
-    fn test_part1() {
-        init_tests();
-        assert_eq!(
-            part1(include_str!("../example.txt")).expect("success"),
-            vec![4, 6, 3, 5, 6, 3, 5, 2, 1, 0]
-        );
+    fn invalid_programs() {
+        let input = |program: &str| {
+            parse_input(&format!(
+                "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: {}\n",
+                program
+            ))
+            .map(|p| p.raw_program)
+        };
+
+        assert_eq!(input("0,1,5,4"), Ok(vec![0, 1, 5, 4]));
+        assert_eq!(input("0,7"), Err(InputParseError::InvalidOperandOpcode(7)));
+        assert_eq!(input("0,1,5"), Err(InputParseError::InvalidDecodeLength));
+        assert_eq!(input("5,4,3,1"), Err(InputParseError::OddJumpTarget(1)));
     }
 
-    #[test]
-    fn test_part2() {
-        init_tests();
-        assert_eq!(
-            part2(include_str!("../example2.txt")).expect("success"),
-            117440
-        );
-    }
+    aoc_support::example_tests!(Solver);
 }
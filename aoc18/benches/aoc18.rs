@@ -1,5 +1,10 @@
+use std::sync::LazyLock;
+
 use divan::black_box;
 
+static INPUT: LazyLock<String> =
+    LazyLock::new(|| aoc_support::input::from_inputs_dir(18).expect("benchmark input"));
+
 fn main() {
     // Run registered benchmarks.
     divan::main();
@@ -7,20 +12,10 @@ fn main() {
 
 #[divan::bench]
 fn part1() {
-    aoc18::part1(
-        black_box(include_str!("../input.txt")),
-        (71, 71).into(),
-        1024,
-    )
-    .unwrap();
+    aoc18::part1(black_box(INPUT.as_str()), (71, 71).into(), 1024).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    aoc18::part2(
-        black_box(include_str!("../input.txt")),
-        (71, 71).into(),
-        1024,
-    )
-    .unwrap();
+    aoc18::part2(black_box(INPUT.as_str()), (71, 71).into(), 1024).unwrap();
 }
@@ -1,6 +1,9 @@
-use std::{collections::HashSet, fmt::Display};
-
+use aoc_support::{
+    error::{parse_complete, ProcessingError},
+    Solution,
+};
 use glam::IVec2;
+use map_parse::Map;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending},
@@ -9,183 +12,167 @@ use nom::{
     Parser as _,
 };
 use nom_supreme::ParserExt;
-use pathfinding::prelude::dijkstra;
-
-#[derive(thiserror::Error, Debug, PartialEq)]
-enum InputParseError {
-    #[error("Failed to parse using Nom")]
-    NomError(#[source] nom::Err<nom::error::Error<String>>),
+use serde::Deserialize;
 
-    #[error("Unparsed data remained: {0:?}")]
-    UnparsedData(String),
+pub struct Input {
+    positions: Vec<IVec2>,
 }
 
-struct Input {
-    positions: Vec<IVec2>,
+#[derive(thiserror::Error, Debug, PartialEq)]
+enum GridError {
+    #[error("Byte {index} falls at {position}, outside of the {grid_size} grid")]
+    OutsideGrid {
+        index: usize,
+        position: IVec2,
+        grid_size: IVec2,
+    },
 }
 
-fn parse_input(s: &str) -> Result<Input, InputParseError> {
-    let (rest, input) = separated_list1(
-        line_ending,
-        separated_pair(complete::i32, tag(","), complete::i32).map(|(x, y)| IVec2::new(x, y)),
+fn parse_input(s: &str) -> Result<Input, ProcessingError> {
+    parse_complete(
+        separated_list1(
+            line_ending,
+            separated_pair(complete::i32, tag(","), complete::i32).map(|(x, y)| IVec2::new(x, y)),
+        )
+        .terminated(many0(line_ending))
+        .map(|positions| Input { positions }),
+        s,
     )
-    .terminated(many0(line_ending))
-    .map(|positions| Input { positions })
-    .parse(s)?;
+}
 
-    if !rest.is_empty() {
-        return Err(InputParseError::UnparsedData(rest.into()));
+/// Map of which positions are blocked after the first `simulation` bytes fell
+fn blocked_map(
+    grid_size: IVec2,
+    positions: &[IVec2],
+    simulation: usize,
+) -> Result<Map<bool>, GridError> {
+    let mut m = Map::filled(grid_size.y as usize, grid_size.x as usize, false);
+    for (index, p) in positions.iter().take(simulation).enumerate() {
+        *m.get_mut(p).ok_or(GridError::OutsideGrid {
+            index,
+            position: *p,
+            grid_size,
+        })? = true;
     }
+    Ok(m)
+}
 
-    Ok(input)
+fn shortest_path_len(g: &Map<bool>) -> Option<usize> {
+    let goal = IVec2::new(g.col_count() as i32 - 1, g.row_count() as i32 - 1);
+    g.shortest_path_len(IVec2::new(0, 0), goal, |_, blocked| !blocked)
 }
 
-impl<INNER: Into<String>> From<nom::Err<nom::error::Error<INNER>>> for InputParseError {
-    fn from(value: nom::Err<nom::error::Error<INNER>>) -> Self {
-        InputParseError::NomError(value.map_input(|i| i.into()))
+impl Input {
+    fn has_path(&self, grid_size: IVec2, simulation_size: usize) -> Result<bool, GridError> {
+        Ok(shortest_path_len(&blocked_map(grid_size, &self.positions, simulation_size)?).is_some())
     }
-}
 
-struct Grid {
-    rows: i32,
-    cols: i32,
-    blocks: HashSet<IVec2>,
-}
+    /// The first byte that cuts off the exit
+    fn first_blocking(&self, grid_size: IVec2) -> Result<IVec2, GridError> {
+        let mut low = 0; // possible
+        let mut high = self.positions.len() + 1; // impossible
+
+        while (high - low) > 1 {
+            let mid = (high + low) / 2;
 
-impl Display for Grid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.rows {
-            let mut s = String::with_capacity((self.cols + 1) as usize);
-            for x in 0..self.cols {
-                if self.blocks.contains(&(x, y).into()) {
-                    s.push('#');
-                } else {
-                    s.push('.');
-                }
+            if self.has_path(grid_size, mid)? {
+                low = mid;
+            } else {
+                high = mid;
             }
-            s.push('\n');
-            f.write_str(&s)?;
         }
-        Ok(())
+        Ok(*self.positions.get(low).expect("valid index"))
     }
 }
 
-pub fn part1(input: &str, grid_size: IVec2, simulation: usize) -> color_eyre::Result<usize> {
-    let input = parse_input(input)?;
-
-    // GRID SIZE: 6x6 OR 70x70
-    let g = Grid {
-        rows: grid_size.y,
-        cols: grid_size.x,
-        blocks: input.positions.into_iter().take(simulation).collect(),
-    };
-
-    let goal = IVec2::new(g.rows - 1, g.cols - 1);
-
-    tracing::info!("GRID:\n{}", g);
-    let node_map = dijkstra(
-        &IVec2::new(0, 0),
-        |start| {
-            [(0, 1), (0, -1), (1, 0), (-1, 0)]
-                .into_iter()
-                .map(|(x, y)| start + IVec2::new(x, y))
-                .filter(|p| {
-                    p.x >= 0 && p.x < g.cols && p.y >= 0 && p.y < g.rows && !g.blocks.contains(p)
-                })
-                .map(|p| (p, 1))
-                .collect::<Vec<_>>()
-        },
-        |x| x == &goal,
-    );
-
-    tracing::info!("MAP: {:?}", node_map);
+/// Memory size and how many bytes fall before part 1 looks for a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct Params {
+    pub grid_size: IVec2,
+    pub simulation: usize,
+}
 
-    Ok(node_map.map(|(_, len)| len).unwrap_or(0))
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            grid_size: IVec2::new(71, 71),
+            simulation: 1024,
+        }
+    }
 }
 
-impl Input {
-    fn has_path(&self, grid_size: IVec2, simulation_size: usize) -> bool {
-        // GRID SIZE: 6x6 OR 70x70
-        let g = Grid {
-            rows: grid_size.y,
-            cols: grid_size.x,
-            blocks: self
-                .positions
-                .iter()
-                .take(simulation_size)
-                .copied()
-                .collect(),
-        };
+pub struct Solver;
 
-        let goal = IVec2::new(g.rows - 1, g.cols - 1);
-
-        dijkstra(
-            &IVec2::new(0, 0),
-            |start| {
-                [(0, 1), (0, -1), (1, 0), (-1, 0)]
-                    .into_iter()
-                    .map(|(x, y)| start + IVec2::new(x, y))
-                    .filter(|p| {
-                        p.x >= 0
-                            && p.x < g.cols
-                            && p.y >= 0
-                            && p.y < g.rows
-                            && !g.blocks.contains(p)
-                    })
-                    .map(|p| (p, 1))
-                    .collect::<Vec<_>>()
-            },
-            |x| x == &goal,
-        )
-        .is_some()
+impl Solution for Solver {
+    const DAY: u8 = 18;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = String;
+    type Params = Params;
+
+    fn parse(input: &str) -> color_eyre::Result<Input> {
+        Ok(parse_input(input)?)
     }
-}
 
-pub fn part2(input: &str, grid_size: IVec2, simulation: usize) -> color_eyre::Result<IVec2> {
-    let input = parse_input(input)?;
+    fn part1(input: &Input, params: &Params) -> color_eyre::Result<usize> {
+        // GRID SIZE: 6x6 OR 70x70
+        let g = blocked_map(params.grid_size, &input.positions, params.simulation)?;
 
-    let mut low = 0; // possible
-    let mut high = input.positions.len() + 1; // impossible
+        tracing::info!(
+            "GRID:\n{}",
+            g.render_with(|_, blocked| if *blocked { '#' } else { '.' })
+        );
+        let len = shortest_path_len(&g);
 
-    while (high - low) > 1 {
-        let mid = (high + low) / 2;
+        tracing::info!("PATH LENGTH: {:?}", len);
 
-        if input.has_path(grid_size, mid) {
-            low = mid;
-        } else {
-            high = mid;
-        }
+        Ok(len.unwrap_or(0))
+    }
+
+    fn part2(input: &Input, params: &Params) -> color_eyre::Result<String> {
+        let p = input.first_blocking(params.grid_size)?;
+        Ok(format!("{},{}", p.x, p.y))
     }
-    Ok(*input.positions.get(low).expect("valid index"))
+}
+
+pub fn part1(input: &str, grid_size: IVec2, simulation: usize) -> color_eyre::Result<usize> {
+    Solver::solve1(
+        input,
+        &Params {
+            grid_size,
+            simulation,
+        },
+    )
+}
+
+pub fn part2(input: &str, grid_size: IVec2, _simulation: usize) -> color_eyre::Result<IVec2> {
+    Ok(Solver::parse(input)?.first_blocking(grid_size)?)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Once;
-
-    static INIT: Once = Once::new();
-    pub fn init_tests() {
-        INIT.call_once(|| {
-            color_eyre::install().unwrap_or(());
-        });
-    }
 
     #[test]
-    fn test_part1() {
-        init_tests();
-        assert_eq!(
-            part1(include_str!("../example.txt"), (7, 7).into(), 12).expect("success"),
-            22
-        );
-    }
+    fn bytes_outside_the_grid() {
+        let input = Solver::parse("1,1\n7,2\n").expect("valid input");
+        let params = Params {
+            grid_size: IVec2::new(7, 7),
+            simulation: 2,
+        };
 
-    #[test]
-    fn test_part2() {
-        init_tests();
+        let err = Solver::part1(&input, &params).expect_err("7,2 is outside");
         assert_eq!(
-            part2(include_str!("../example.txt"), (7, 7).into(), 25).expect("success"),
-            IVec2::new(6, 1)
+            err.downcast_ref::<GridError>(),
+            Some(&GridError::OutsideGrid {
+                index: 1,
+                position: IVec2::new(7, 2),
+                grid_size: IVec2::new(7, 7),
+            })
         );
+        assert!(Solver::part2(&input, &params).is_err());
     }
+
+    aoc_support::example_tests!(Solver);
 }
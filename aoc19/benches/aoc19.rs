@@ -1,5 +1,10 @@
+use std::sync::LazyLock;
+
 use divan::black_box;
 
+static INPUT: LazyLock<String> =
+    LazyLock::new(|| aoc_support::input::from_inputs_dir(19).expect("benchmark input"));
+
 fn main() {
     // Run registered benchmarks.
     divan::main();
@@ -7,10 +12,10 @@ fn main() {
 
 #[divan::bench]
 fn part1() {
-    aoc19::part1(black_box(include_str!("../input.txt"))).unwrap();
+    aoc19::part1(black_box(INPUT.as_str())).unwrap();
 }
 
 #[divan::bench]
 fn part2() {
-    aoc19::part2(black_box(include_str!("../input.txt"))).unwrap();
+    aoc19::part2(black_box(INPUT.as_str())).unwrap();
 }
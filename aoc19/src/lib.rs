@@ -1,5 +1,9 @@
 use std::collections::HashMap;
 
+use aoc_support::{
+    error::{parse_complete, ProcessingError},
+    Solution,
+};
 use nom::{
     bytes::complete::{is_a, tag},
     character::complete::line_ending,
@@ -8,24 +12,14 @@ use nom::{
     IResult, Parser as _,
 };
 use nom_supreme::ParserExt;
-use rayon::prelude::*;
-
-#[derive(thiserror::Error, Debug, PartialEq)]
-enum InputParseError {
-    #[error("Failed to parse using Nom")]
-    NomError(#[source] nom::Err<nom::error::Error<String>>),
-
-    #[error("Unparsed data remained: {0:?}")]
-    UnparsedData(String),
-}
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Stripe {
     pattern: String,
 }
 
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     available: Vec<Stripe>,
     required: Vec<Stripe>,
 }
@@ -38,28 +32,18 @@ fn parse_stripe(s: &str) -> IResult<&str, Stripe> {
         .parse(s)
 }
 
-fn parse_input(s: &str) -> Result<Input, InputParseError> {
-    let (rest, input) = tuple((
-        separated_list1(tag(", "), parse_stripe).terminated(many0(line_ending)),
-        separated_list1(line_ending, parse_stripe).terminated(many0(line_ending)),
-    ))
-    .map(|(available, required)| Input {
-        available,
-        required,
-    })
-    .parse(s)?;
-
-    if !rest.is_empty() {
-        return Err(InputParseError::UnparsedData(rest.into()));
-    }
-
-    Ok(input)
-}
-
-impl<INNER: Into<String>> From<nom::Err<nom::error::Error<INNER>>> for InputParseError {
-    fn from(value: nom::Err<nom::error::Error<INNER>>) -> Self {
-        InputParseError::NomError(value.map_input(|i| i.into()))
-    }
+fn parse_input(s: &str) -> Result<Input, ProcessingError> {
+    parse_complete(
+        tuple((
+            separated_list1(tag(", "), parse_stripe).terminated(many0(line_ending)),
+            separated_list1(line_ending, parse_stripe).terminated(many0(line_ending)),
+        ))
+        .map(|(available, required)| Input {
+            available,
+            required,
+        }),
+        s,
+    )
 }
 
 #[derive(Default)]
@@ -92,57 +76,58 @@ impl GoalCache {
     }
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 19;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Params = ();
+
+    fn parse(input: &str) -> color_eyre::Result<Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Input, _: &()) -> color_eyre::Result<usize> {
+        let mut cache = GoalCache {
+            choices: input.available.clone(),
+            ..Default::default()
+        };
+
+        Ok(input
+            .required
+            .iter()
+            .filter(|v| cache.can_build(&v.pattern) > 0)
+            .count())
+    }
+
+    fn part2(input: &Input, _: &()) -> color_eyre::Result<usize> {
+        let mut cache = GoalCache {
+            choices: input.available.clone(),
+            ..Default::default()
+        };
+
+        Ok(input
+            .required
+            .iter()
+            .map(|v| cache.can_build(&v.pattern))
+            .sum())
+    }
+}
+
 pub fn part1(input: &str) -> color_eyre::Result<usize> {
-    let input = parse_input(input)?;
-
-    let mut cache = GoalCache {
-        choices: input.available,
-        ..Default::default()
-    };
-
-    Ok(input
-        .required
-        .iter()
-        .filter(|v| cache.can_build(&v.pattern) > 0)
-        .count())
+    Solver::solve1(input, &())
 }
 
 pub fn part2(input: &str) -> color_eyre::Result<usize> {
-    let input = parse_input(input)?;
-
-    let mut cache = GoalCache {
-        choices: input.available,
-        ..Default::default()
-    };
-
-    Ok(input
-        .required
-        .iter()
-        .map(|v| cache.can_build(&v.pattern))
-        .sum())
+    Solver::solve2(input, &())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Once;
-
-    static INIT: Once = Once::new();
-    pub fn init_tests() {
-        INIT.call_once(|| {
-            color_eyre::install().unwrap_or(());
-        });
-    }
 
-    #[test_log::test]
-    fn test_part1() {
-        init_tests();
-        assert_eq!(part1(include_str!("../example.txt")).expect("success"), 6);
-    }
-
-    #[test]
-    fn test_part2() {
-        init_tests();
-        assert_eq!(part2(include_str!("../example.txt")).expect("success"), 16);
-    }
+    aoc_support::example_tests!(Solver);
 }
@@ -1,11 +1,13 @@
 use std::iter::zip;
 
+use aoc_support::Solution;
+use color_eyre::eyre::ensure;
 use itertools::enumerate;
 use parse::input;
 use tracing::{event, instrument, Level};
 
 #[derive(Debug, PartialEq)]
-struct Input {
+pub struct Input {
     levels: Vec<Vec<u32>>,
 }
 
@@ -137,26 +139,41 @@ fn is_safe_by_removal(vec: &[u32]) -> bool {
     is_safe_level(vec)
 }
 
-pub fn part1(s: &str) -> usize {
-    let (r, data) = input(s).expect("good input");
-    assert!(r.is_empty());
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 2;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Params = ();
+
+    fn parse(s: &str) -> color_eyre::Result<Input> {
+        let (r, data) = input(s).map_err(|e| e.to_owned())?;
+        ensure!(r.is_empty(), "Unparsed data remained: {:?}", r);
+        Ok(data)
+    }
 
-    data.levels.into_iter().filter(|x| is_safe_level(x)).count()
+    fn part1(data: &Input, _: &()) -> color_eyre::Result<usize> {
+        Ok(data.levels.iter().filter(|x| is_safe_level(x)).count())
+    }
+
+    fn part2(data: &Input, _: &()) -> color_eyre::Result<usize> {
+        Ok(data.levels.iter().filter(|x| is_safe_by_removal(x)).count())
+    }
 }
 
-pub fn part2(s: &str) -> usize {
-    let (r, data) = input(s).expect("good input");
-    assert!(r.is_empty());
+pub fn part1(s: &str) -> usize {
+    Solver::solve1(s, &()).expect("good input")
+}
 
-    data.levels
-        .into_iter()
-        .filter(|x| is_safe_by_removal(x))
-        .count()
+pub fn part2(s: &str) -> usize {
+    Solver::solve2(s, &()).expect("good input")
 }
 
 #[cfg(test)]
 mod tests {
-
     use super::parse::*;
     use super::*;
 
@@ -197,13 +214,5 @@ mod tests {
         assert!(!safe_level_if_remove(&v, 3));
     }
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(include_str!("../example.txt")), 2);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(include_str!("../example.txt")), 4);
-    }
+    aoc_support::example_tests!(Solver);
 }
@@ -1,5 +1,3 @@
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
-
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
 static ALLOC: dhat::Alloc = dhat::Alloc;
@@ -11,18 +9,9 @@ fn main() -> color_eyre::eyre::Result<()> {
 
     color_eyre::install()?;
 
-    let stdout_log = tracing_subscriber::fmt::layer().compact();
-
-    tracing_subscriber::registry()
-        .with(stdout_log)
-        .with(EnvFilter::from_default_env())
-        .init();
-
-    let s1 = aoc20::part1(include_str!("../input.txt"))?;
-    println!("Part 1: {}", s1);
+    aoc_support::binary::init_tracing();
 
-    let s2 = aoc20::part2(include_str!("../input.txt"))?;
-    println!("Part 2: {}", s2);
+    let input = aoc_support::input::load(20)?;
 
-    Ok(())
+    aoc_support::binary::solve::<aoc20::Solver>(&input)
 }
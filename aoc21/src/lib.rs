@@ -3,10 +3,11 @@ use std::{
     hash::Hash,
 };
 
+use aoc_support::{error::parse_complete, Solution};
 use glam::IVec2;
 use nom::{
     bytes::complete::is_a,
-    character::complete::line_ending,
+    character::complete::{line_ending, space0},
     multi::{many0, separated_list1},
     Parser,
 };
@@ -14,42 +15,34 @@ use nom_supreme::ParserExt;
 
 #[derive(thiserror::Error, Debug, PartialEq)]
 enum ProcessingError {
-    #[error("Failed to parse using Nom")]
-    NomError(#[source] nom::Err<nom::error::Error<String>>),
-
-    #[error("Unparsed data remained: {0:?}")]
-    UnparsedData(String),
+    #[error(transparent)]
+    Parse(#[from] aoc_support::error::ProcessingError),
 
     #[error("No coordinates for: {0:?} (invalid character?)")]
     InvalidCharacter(char),
 }
 
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     inputs: Vec<String>,
 }
 
 fn parse_input(s: &str) -> Result<Input, ProcessingError> {
-    let (rest, inputs) = separated_list1(
-        line_ending,
-        is_a("0123456789A").map(|s: &str| s.to_string()),
-    )
-    .terminated(many0(line_ending))
-    .parse(s)?;
-
-    if !rest.is_empty() {
-        return Err(ProcessingError::UnparsedData(rest.into()));
-    }
+    let inputs = parse_complete(
+        separated_list1(
+            line_ending,
+            // some inputs have trailing spaces
+            is_a("0123456789A")
+                .map(|s: &str| s.to_string())
+                .terminated(space0),
+        )
+        .terminated(many0(line_ending)),
+        s,
+    )?;
 
     Ok(Input { inputs })
 }
 
-impl<INNER: Into<String>> From<nom::Err<nom::error::Error<INNER>>> for ProcessingError {
-    fn from(value: nom::Err<nom::error::Error<INNER>>) -> Self {
-        ProcessingError::NomError(value.map_input(|i| i.into()))
-    }
-}
-
 #[derive(Default)]
 struct KeyPad {
     coord: HashMap<char, IVec2>,
@@ -192,58 +185,69 @@ fn code_number(target: &str) -> usize {
     target.split_at(3).0.parse::<usize>().expect("valid number")
 }
 
-pub fn part1(input: &str) -> color_eyre::Result<usize> {
-    let input = parse_input(input)?;
-
-    let mut pads = Vec::new();
-
-    pads.push(KeyPad::new_button_pad());
-    pads.push(KeyPad::new_arrow_pad());
-    pads.push(KeyPad::new_arrow_pad());
-
-    Ok(input
-        .inputs
-        .iter()
-        .map(|code| {
-            let number = code_number(code);
-            let cnt = short_path_count(code, &pads, &mut HashMap::new());
-            number * cnt
-        })
-        .sum())
-}
+pub struct Solver;
 
-pub fn part2(input: &str) -> color_eyre::Result<usize> {
-    let input = parse_input(input)?;
+impl Solution for Solver {
+    const DAY: u8 = 21;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Params = ();
+
+    fn parse(input: &str) -> color_eyre::Result<Input> {
+        Ok(parse_input(input)?)
+    }
 
-    let mut pads = Vec::new();
+    fn part1(input: &Input, _: &()) -> color_eyre::Result<usize> {
+        let mut pads = Vec::new();
 
-    pads.push(KeyPad::new_button_pad());
-    for _ in 0..25 {
+        pads.push(KeyPad::new_button_pad());
         pads.push(KeyPad::new_arrow_pad());
+        pads.push(KeyPad::new_arrow_pad());
+
+        Ok(input
+            .inputs
+            .iter()
+            .map(|code| {
+                let number = code_number(code);
+                let cnt = short_path_count(code, &pads, &mut HashMap::new());
+                number * cnt
+            })
+            .sum())
+    }
+
+    fn part2(input: &Input, _: &()) -> color_eyre::Result<usize> {
+        let mut pads = Vec::new();
+
+        pads.push(KeyPad::new_button_pad());
+        for _ in 0..25 {
+            pads.push(KeyPad::new_arrow_pad());
+        }
+
+        Ok(input
+            .inputs
+            .iter()
+            .map(|code| {
+                let number = code_number(code);
+                let cnt = short_path_count(code, &pads, &mut HashMap::new());
+                number * cnt
+            })
+            .sum())
     }
+}
+
+pub fn part1(input: &str) -> color_eyre::Result<usize> {
+    Solver::solve1(input, &())
+}
 
-    Ok(input
-        .inputs
-        .iter()
-        .map(|code| {
-            let number = code_number(code);
-            let cnt = short_path_count(code, &pads, &mut HashMap::new());
-            number * cnt
-        })
-        .sum())
+pub fn part2(input: &str) -> color_eyre::Result<usize> {
+    Solver::solve2(input, &())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Once;
-
-    static INIT: Once = Once::new();
-    pub fn init_tests() {
-        INIT.call_once(|| {
-            color_eyre::install().unwrap_or(());
-        });
-    }
 
     #[test_log::test]
     fn test_short_path() {
@@ -273,13 +277,13 @@ mod tests {
         );
     }
 
-    #[test_log::test]
-    fn test_part1() {
-        init_tests();
-
+    #[test]
+    fn trailing_spaces() {
         assert_eq!(
-            part1(include_str!("../example.txt")).expect("success"),
-            126384
+            parse_input("382A \n463A\n").expect("valid input").inputs,
+            ["382A", "463A"]
         );
     }
+
+    aoc_support::example_tests!(Solver);
 }
@@ -1,37 +1,20 @@
 use std::collections::HashMap;
 
+use aoc_support::{
+    error::{parse_complete, ProcessingError},
+    Solution,
+};
 use nom::{
     character::complete::{self, line_ending},
     multi::{many0, separated_list1},
-    Parser as _,
 };
 use nom_supreme::ParserExt;
 
-#[derive(thiserror::Error, Debug, PartialEq)]
-enum ProcessingError {
-    #[error("Failed to parse using Nom")]
-    NomError(#[source] nom::Err<nom::error::Error<String>>),
-
-    #[error("Unparsed data remained: {0:?}")]
-    UnparsedData(String),
-}
-
 fn parse_input(s: &str) -> Result<Vec<u32>, ProcessingError> {
-    let (rest, values) = separated_list1(line_ending, complete::u32)
-        .terminated(many0(line_ending))
-        .parse(s)?;
-
-    if !rest.is_empty() {
-        return Err(ProcessingError::UnparsedData(rest.into()));
-    }
-
-    Ok(values)
-}
-
-impl<INNER: Into<String>> From<nom::Err<nom::error::Error<INNER>>> for ProcessingError {
-    fn from(value: nom::Err<nom::error::Error<INNER>>) -> Self {
-        ProcessingError::NomError(value.map_input(|i| i.into()))
-    }
+    parse_complete(
+        separated_list1(line_ending, complete::u32).terminated(many0(line_ending)),
+        s,
+    )
 }
 
 fn next(value: u32) -> u32 {
@@ -49,17 +32,6 @@ fn nth_next(value: u32, cnt: u32) -> u32 {
     v
 }
 
-pub fn part1(input: &str) -> color_eyre::Result<usize> {
-    let input = parse_input(input)?;
-
-    let mut cnt = 0;
-    for v in input {
-        cnt += nth_next(v, 2000) as usize;
-    }
-
-    Ok(cnt)
-}
-
 #[derive(Hash, PartialEq, Eq, Debug, PartialOrd, Default, Copy, Clone)]
 struct Seq {
     a: i32,
@@ -85,53 +57,77 @@ impl Seq {
     }
 }
 
-pub fn part2(input: &str) -> color_eyre::Result<u32> {
-    let input = parse_input(input)?;
+pub struct Solver;
 
-    let mut seq_wins = HashMap::new();
+impl Solution for Solver {
+    const DAY: u8 = 22;
 
-    for x in input {
-        // need: all sequences of 5 numbers in the first 2000 steps
-        let mut first_per_seq = HashMap::new();
+    type Input = Vec<u32>;
+    type Answer1 = usize;
+    type Answer2 = u32;
+    type Params = ();
 
-        let mut seq = Seq {
-            ..Default::default()
-        };
+    fn parse(input: &str) -> color_eyre::Result<Vec<u32>> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Vec<u32>, _: &()) -> color_eyre::Result<usize> {
+        let mut cnt = 0;
+        for &v in input {
+            cnt += nth_next(v, 2000) as usize;
+        }
+
+        Ok(cnt)
+    }
 
-        let mut v = x;
-        for cnt in 0..=2000 {
-            let n = next(v);
-            let win = n % 10;
+    fn part2(input: &Vec<u32>, _: &()) -> color_eyre::Result<u32> {
+        let mut seq_wins = HashMap::new();
 
-            seq.push_next(v, n);
+        for &x in input {
+            // need: all sequences of 5 numbers in the first 2000 steps
+            let mut first_per_seq = HashMap::new();
 
-            if cnt >= 3 {
-                // we have enough numbers to check
-                first_per_seq.entry(seq).or_insert(win);
+            let mut seq = Seq {
+                ..Default::default()
+            };
+
+            let mut v = x;
+            for cnt in 0..=2000 {
+                let n = next(v);
+                let win = n % 10;
+
+                seq.push_next(v, n);
+
+                if cnt >= 3 {
+                    // we have enough numbers to check
+                    first_per_seq.entry(seq).or_insert(win);
+                }
+                v = n;
             }
-            v = n;
-        }
 
-        for (k, v) in first_per_seq.iter() {
-            seq_wins.entry(*k).and_modify(|x| *x += *v).or_insert(*v);
+            for (k, v) in first_per_seq.iter() {
+                seq_wins.entry(*k).and_modify(|x| *x += *v).or_insert(*v);
+            }
         }
+        let m = *seq_wins.values().max().expect("some value");
+
+        Ok(m)
     }
-    let m = *seq_wins.values().max().expect("some value");
+}
 
-    Ok(m)
+pub fn part1(input: &str) -> color_eyre::Result<usize> {
+    Solver::solve1(input, &())
+}
+
+pub fn part2(input: &str) -> color_eyre::Result<u32> {
+    Solver::solve2(input, &())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use std::sync::Once;
+    use aoc_support::testing::init_tests;
 
-    static INIT: Once = Once::new();
-    pub fn init_tests() {
-        INIT.call_once(|| {
-            color_eyre::install().unwrap_or(());
-        });
-    }
+    use super::*;
 
     #[test]
     fn test_next() {
@@ -141,18 +137,5 @@ mod tests {
         assert_eq!(next(16495136), 527345);
     }
 
-    #[test]
-    fn test_part1() {
-        init_tests();
-        assert_eq!(
-            part1(include_str!("../example.txt")).expect("success"),
-            37327623
-        );
-    }
-
-    #[test]
-    fn test_part2() {
-        init_tests();
-        assert_eq!(part2(include_str!("../example2.txt")).expect("success"), 23);
-    }
+    aoc_support::example_tests!(Solver);
 }
@@ -1,25 +1,19 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_support::{
+    error::{parse_complete, ProcessingError},
+    Solution,
+};
 use nom::{
     bytes::complete::{is_not, tag},
     character::complete::line_ending,
     multi::{many0, separated_list1},
     sequence::separated_pair,
-    Parser,
 };
 use nom_supreme::ParserExt;
 use petgraph::graph::{NodeIndex, UnGraph};
 
-#[derive(thiserror::Error, Debug, PartialEq)]
-enum ProcessingError {
-    #[error("Failed to parse using Nom")]
-    NomError(#[source] nom::Err<nom::error::Error<String>>),
-
-    #[error("Unparsed data remained: {0:?}")]
-    UnparsedData(String),
-}
-
-struct Input {
+pub struct Input {
     node_indexes: HashMap<String, NodeIndex<u32>>,
     graph: UnGraph<String, ()>,
 }
@@ -28,12 +22,14 @@ fn parse_input(s: &str) -> Result<Input, ProcessingError> {
     let mut node_indexes = HashMap::new();
     let mut graph = UnGraph::<String, ()>::default();
 
-    let (rest, items) = separated_list1(
-        line_ending,
-        separated_pair(is_not("\n\r-"), tag("-"), is_not("\n\r-")),
-    )
-    .terminated(many0(line_ending))
-    .parse(s)?;
+    let items = parse_complete(
+        separated_list1(
+            line_ending,
+            separated_pair(is_not("\n\r-"), tag("-"), is_not("\n\r-")),
+        )
+        .terminated(many0(line_ending)),
+        s,
+    )?;
 
     for (a, b) in items {
         let a_idx = *node_indexes
@@ -46,127 +42,117 @@ fn parse_input(s: &str) -> Result<Input, ProcessingError> {
         graph.add_edge(a_idx, b_idx, ());
     }
 
-    if !rest.is_empty() {
-        return Err(ProcessingError::UnparsedData(rest.into()));
-    }
-
     Ok(Input {
         node_indexes,
         graph,
     })
 }
 
-impl<INNER: Into<String>> From<nom::Err<nom::error::Error<INNER>>> for ProcessingError {
-    fn from(value: nom::Err<nom::error::Error<INNER>>) -> Self {
-        ProcessingError::NomError(value.map_input(|i| i.into()))
-    }
-}
+pub struct Solver;
 
-pub fn part1(input: &str) -> color_eyre::Result<usize> {
-    let input = parse_input(input)?;
+impl Solution for Solver {
+    const DAY: u8 = 23;
 
-    let mut interconnected = HashSet::new();
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = String;
+    type Params = ();
 
-    for (a_name, a_idx) in input.node_indexes.iter() {
-        for b_idx in input.graph.neighbors(*a_idx) {
-            let b_name = input.graph.node_weight(b_idx).expect("valid");
-            for c_idx in input.graph.neighbors(b_idx).filter(|idx| idx != a_idx) {
-                let c_name = input.graph.node_weight(c_idx).expect("valid");
+    fn parse(input: &str) -> color_eyre::Result<Input> {
+        Ok(parse_input(input)?)
+    }
 
-                // C must be connected to a as well
-                if input.graph.find_edge(*a_idx, c_idx).is_none() {
-                    continue;
-                }
+    fn part1(input: &Input, _: &()) -> color_eyre::Result<usize> {
+        let mut interconnected = HashSet::new();
 
-                if !a_name.starts_with("t") && !b_name.starts_with("t") && !c_name.starts_with("t")
-                {
-                    continue;
-                }
+        for (a_name, a_idx) in input.node_indexes.iter() {
+            for b_idx in input.graph.neighbors(*a_idx) {
+                let b_name = input.graph.node_weight(b_idx).expect("valid");
+                for c_idx in input.graph.neighbors(b_idx).filter(|idx| idx != a_idx) {
+                    let c_name = input.graph.node_weight(c_idx).expect("valid");
+
+                    // C must be connected to a as well
+                    if input.graph.find_edge(*a_idx, c_idx).is_none() {
+                        continue;
+                    }
 
-                let mut v = vec![a_name.clone(), b_name.clone(), c_name.clone()];
-                v.sort();
+                    if !a_name.starts_with("t")
+                        && !b_name.starts_with("t")
+                        && !c_name.starts_with("t")
+                    {
+                        continue;
+                    }
 
-                interconnected.insert(v);
+                    let mut v = vec![a_name.clone(), b_name.clone(), c_name.clone()];
+                    v.sort();
+
+                    interconnected.insert(v);
+                }
             }
         }
-    }
 
-    Ok(interconnected.len())
-}
+        Ok(interconnected.len())
+    }
 
-pub fn part2(input: &str) -> color_eyre::Result<String> {
-    let input = parse_input(input)?;
-
-    let mut best_len = 0;
-    let mut best_set = HashSet::new();
-
-    // start with 2 connected computers, and see how large we can make the set, ever
-    for (a_name, a_idx) in input.node_indexes.iter() {
-        let mut large_set = HashSet::from([*a_idx]);
-        let mut found = true;
-
-        while found {
-            found = false;
-
-            for other in input.graph.neighbors(*a_idx) {
-                // find out if this is suitable
-                if large_set
-                    .iter()
-                    .any(|n| input.graph.find_edge(*n, other).is_none())
-                {
-                    continue;
+    fn part2(input: &Input, _: &()) -> color_eyre::Result<String> {
+        let mut best_len = 0;
+        let mut best_set = HashSet::new();
+
+        // start with 2 connected computers, and see how large we can make the set, ever
+        for a_idx in input.node_indexes.values() {
+            let mut large_set = HashSet::from([*a_idx]);
+            let mut found = true;
+
+            while found {
+                found = false;
+
+                for other in input.graph.neighbors(*a_idx) {
+                    // find out if this is suitable
+                    if large_set
+                        .iter()
+                        .any(|n| input.graph.find_edge(*n, other).is_none())
+                    {
+                        continue;
+                    }
+                    large_set.insert(other);
+                    found = true;
                 }
-                large_set.insert(other);
-                found = true;
+            }
+            if large_set.len() > best_len {
+                best_len = large_set.len();
+                best_set = large_set;
             }
         }
-        if large_set.len() > best_len {
-            best_len = large_set.len();
-            best_set = large_set;
-        }
-    }
 
-    let mut items = best_set
-        .iter()
-        .map(|idx| input.graph.node_weight(*idx).expect("valid index"))
-        .collect::<Vec<_>>();
-    items.sort();
+        let mut items = best_set
+            .iter()
+            .map(|idx| input.graph.node_weight(*idx).expect("valid index"))
+            .collect::<Vec<_>>();
+        items.sort();
 
-    let mut result = String::new();
-    for x in items {
-        if !result.is_empty() {
-            result.push(',')
+        let mut result = String::new();
+        for x in items {
+            if !result.is_empty() {
+                result.push(',')
+            }
+            result.push_str(x);
         }
-        result.push_str(x);
+
+        Ok(result)
     }
+}
 
-    Ok(result)
+pub fn part1(input: &str) -> color_eyre::Result<usize> {
+    Solver::solve1(input, &())
+}
+
+pub fn part2(input: &str) -> color_eyre::Result<String> {
+    Solver::solve2(input, &())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Once;
-
-    static INIT: Once = Once::new();
-    pub fn init_tests() {
-        INIT.call_once(|| {
-            color_eyre::install().unwrap_or(());
-        });
-    }
-
-    #[test]
-    fn test_part1() {
-        init_tests();
-        assert_eq!(part1(include_str!("../example.txt")).expect("success"), 7);
-    }
 
-    #[test]
-    fn test_part2() {
-        init_tests();
-        assert_eq!(
-            part2(include_str!("../example.txt")).expect("success"),
-            "co,de,ka,ta"
-        );
-    }
+    aoc_support::example_tests!(Solver);
 }
@@ -0,0 +1,423 @@
+//! The gate netlist compiled into a DAG over wire indexes.
+//!
+//! Wires are numbered in name order. Gates are kept in topological order
+//! and evaluated on `u64` values, which runs 64 independent sets of inputs
+//! (lanes) at once. Buses hold numbers of up to 64 bits.
+
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::{Operation, OperationMapping};
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum CircuitError {
+    #[error("Gate for {gate} reads wire {wire}, which is neither an input nor a gate output")]
+    UnknownWire { gate: String, wire: String },
+
+    #[error("Wire {0} is both an input and a gate output")]
+    DrivenInput(String),
+
+    #[error("Gates form a loop: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+
+    #[error("Wire {0} is not the output of a gate")]
+    NotAGateOutput(String),
+
+    #[error("Bus {prefix} is {width} bits wide, at most 64 are supported")]
+    BusTooWide { prefix: char, width: usize },
+
+    #[error("Bus {prefix} has no wire for bit {bit}")]
+    MissingBit { prefix: char, bit: usize },
+
+    #[error("Bus {prefix} has several wires for bit {bit}")]
+    DuplicateBit { prefix: char, bit: usize },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Gate {
+    operation: Operation,
+    op1: usize,
+    op2: usize,
+    output: usize,
+}
+
+/// Where depth first search is for a wire while sorting gates.
+#[derive(Copy, Clone, PartialEq)]
+enum Visit {
+    New,
+    InProgress,
+    Done,
+}
+
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    names: Vec<String>,
+    /// Gate driving each wire, `None` for inputs
+    drivers: Vec<Option<Gate>>,
+    /// Gates, every one after the gates of its operands
+    order: Vec<Gate>,
+}
+
+impl Circuit {
+    /// Compiles the gates, with `inputs` naming the wires that have no gate.
+    pub fn new<'a>(
+        inputs: impl IntoIterator<Item = &'a str>,
+        gates: &HashMap<String, OperationMapping>,
+    ) -> Result<Self, CircuitError> {
+        let inputs = inputs.into_iter().collect::<Vec<_>>();
+        if let Some(wire) = inputs.iter().find(|w| gates.contains_key(**w)) {
+            return Err(CircuitError::DrivenInput(wire.to_string()));
+        }
+
+        let names = inputs
+            .iter()
+            .copied()
+            .chain(gates.keys().map(String::as_str))
+            .sorted()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        let index = names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| (name.as_str(), idx))
+            .collect::<HashMap<_, _>>();
+
+        let mut drivers = vec![None; names.len()];
+        for (output, mapping) in gates {
+            let operand = |wire: &String| {
+                index
+                    .get(wire.as_str())
+                    .copied()
+                    .ok_or_else(|| CircuitError::UnknownWire {
+                        gate: output.clone(),
+                        wire: wire.clone(),
+                    })
+            };
+            let output = index[output.as_str()];
+            drivers[output] = Some(Gate {
+                operation: mapping.operation,
+                op1: operand(&mapping.op1)?,
+                op2: operand(&mapping.op2)?,
+                output,
+            });
+        }
+
+        let mut circuit = Self {
+            names,
+            drivers,
+            order: Vec::new(),
+        };
+        circuit.sort()?;
+        Ok(circuit)
+    }
+
+    /// Fills `order` by depth first search from every wire.
+    ///
+    /// The search keeps its own stack, as gate chains can be far deeper
+    /// than the call stack allows.
+    fn sort(&mut self) -> Result<(), CircuitError> {
+        let mut visits = vec![Visit::New; self.names.len()];
+        // gates being visited, with how many of their operands were entered
+        let mut stack = Vec::new();
+
+        for wire in 0..self.names.len() {
+            self.enter(wire, &mut visits, &mut stack)?;
+
+            while let Some(top) = stack.last_mut() {
+                let (wire, entered) = *top;
+                top.1 += 1;
+
+                let gate = self.drivers[wire].expect("only gates are on the stack");
+                let operand = match entered {
+                    0 => gate.op1,
+                    1 => gate.op2,
+                    _ => {
+                        stack.pop();
+                        visits[wire] = Visit::Done;
+                        self.order.push(gate);
+                        continue;
+                    }
+                };
+                self.enter(operand, &mut visits, &mut stack)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Starts visiting `wire`. Inputs are done right away, gates go on the
+    /// `stack` until their operands are done.
+    fn enter(
+        &self,
+        wire: usize,
+        visits: &mut [Visit],
+        stack: &mut Vec<(usize, usize)>,
+    ) -> Result<(), CircuitError> {
+        match visits[wire] {
+            Visit::Done => {}
+            Visit::InProgress => {
+                let start = stack
+                    .iter()
+                    .position(|(w, _)| *w == wire)
+                    .expect("wire on stack");
+                return Err(CircuitError::Cycle(
+                    stack[start..]
+                        .iter()
+                        .map(|(w, _)| *w)
+                        .chain([wire])
+                        .map(|w| self.names[w].clone())
+                        .collect(),
+                ));
+            }
+            Visit::New if self.drivers[wire].is_none() => visits[wire] = Visit::Done,
+            Visit::New => {
+                visits[wire] = Visit::InProgress;
+                stack.push((wire, 0));
+            }
+        }
+        Ok(())
+    }
+
+    /// Index of the wire called `name`.
+    pub fn wire(&self, name: &str) -> Option<usize> {
+        self.names.binary_search_by(|n| n.as_str().cmp(name)).ok()
+    }
+
+    /// Indexes of the wires `{prefix}00`, `{prefix}01`, ..., lowest bit
+    /// first. Every bit from 0 up needs exactly one wire.
+    pub fn bus(&self, prefix: char) -> Result<Vec<usize>, CircuitError> {
+        let wires = self
+            .names
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, name)| {
+                let bit = name
+                    .strip_prefix(prefix)
+                    .filter(|bit| !bit.is_empty() && bit.chars().all(|c| c.is_ascii_digit()))?;
+                // digits too many for usize are too wide anyway
+                Some((bit.parse::<usize>().unwrap_or(usize::MAX), idx))
+            })
+            .sorted()
+            .collect::<Vec<_>>();
+
+        if let Some((highest, _)) = wires.last() {
+            if *highest >= u64::BITS as usize {
+                return Err(CircuitError::BusTooWide {
+                    prefix,
+                    width: highest.saturating_add(1),
+                });
+            }
+        }
+
+        for (expected, (bit, _)) in wires.iter().enumerate() {
+            if *bit < expected {
+                return Err(CircuitError::DuplicateBit { prefix, bit: *bit });
+            }
+            if *bit > expected {
+                return Err(CircuitError::MissingBit {
+                    prefix,
+                    bit: expected,
+                });
+            }
+        }
+
+        Ok(wires.into_iter().map(|(_, idx)| idx).collect())
+    }
+
+    /// All wires at 0, in every lane.
+    pub fn state(&self) -> Vec<u64> {
+        vec![0; self.names.len()]
+    }
+
+    /// Computes every gate output in `state` from the input wires, for all
+    /// 64 lanes at once.
+    pub fn evaluate(&self, state: &mut [u64]) {
+        for gate in &self.order {
+            let (a, b) = (state[gate.op1], state[gate.op2]);
+            state[gate.output] = match gate.operation {
+                Operation::And => a & b,
+                Operation::Or => a | b,
+                Operation::Xor => a ^ b,
+            };
+        }
+    }
+}
+
+/// Sets the number on `bus` in `lane` of `state`.
+pub fn set_lane(state: &mut [u64], bus: &[usize], lane: usize, value: u64) {
+    for (bit, wire) in bus.iter().enumerate() {
+        if (value >> bit) & 1 != 0 {
+            state[*wire] |= 1 << lane;
+        } else {
+            state[*wire] &= !(1 << lane);
+        }
+    }
+}
+
+/// The number on `bus` in `lane` of `state`.
+pub fn get_lane(state: &[u64], bus: &[usize], lane: usize) -> u64 {
+    bus.iter()
+        .enumerate()
+        .map(|(bit, wire)| ((state[*wire] >> lane) & 1) << bit)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gates(netlist: &[(&str, Operation, &str, &str)]) -> HashMap<String, OperationMapping> {
+        netlist
+            .iter()
+            .map(|(op1, operation, op2, output)| {
+                (
+                    output.to_string(),
+                    OperationMapping {
+                        op1: op1.to_string(),
+                        op2: op2.to_string(),
+                        operation: *operation,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn two_bit_adder() {
+        // gates listed outputs first, sorting has to reorder them
+        let circuit = Circuit::new(
+            ["x00", "x01", "y00", "y01"],
+            &gates(&[
+                ("c01", Operation::Or, "t01", "z02"),
+                ("s01", Operation::And, "c00", "t01"),
+                ("s01", Operation::Xor, "c00", "z01"),
+                ("x01", Operation::Xor, "y01", "s01"),
+                ("x01", Operation::And, "y01", "c01"),
+                ("x00", Operation::Xor, "y00", "z00"),
+                ("x00", Operation::And, "y00", "c00"),
+            ]),
+        )
+        .expect("valid circuit");
+        let bus = |prefix| circuit.bus(prefix).expect("valid bus");
+        let (x, y, z) = (bus('x'), bus('y'), bus('z'));
+        assert_eq!(z.len(), 3);
+        assert_eq!(circuit.wire("x01"), Some(x[1]));
+        assert_eq!(circuit.wire("w00"), None);
+
+        let mut state = circuit.state();
+        let pairs = (0..4).cartesian_product(0..4).collect::<Vec<_>>();
+        for (lane, (a, b)) in pairs.iter().enumerate() {
+            set_lane(&mut state, &x, lane, *a);
+            set_lane(&mut state, &y, lane, *b);
+        }
+        circuit.evaluate(&mut state);
+
+        for (lane, (a, b)) in pairs.iter().enumerate() {
+            assert_eq!(get_lane(&state, &z, lane), a + b, "{} + {}", a, b);
+        }
+    }
+
+    #[test]
+    fn invalid_circuits() {
+        assert_eq!(
+            Circuit::new(["x00"], &gates(&[("x00", Operation::And, "y00", "z00")])).err(),
+            Some(CircuitError::UnknownWire {
+                gate: "z00".into(),
+                wire: "y00".into()
+            })
+        );
+        assert_eq!(
+            Circuit::new(["x00"], &gates(&[("x00", Operation::Or, "x00", "x00")])).err(),
+            Some(CircuitError::DrivenInput("x00".into()))
+        );
+
+        let err = Circuit::new(
+            ["x00"],
+            &gates(&[
+                ("x00", Operation::And, "b", "a"),
+                ("a", Operation::Or, "x00", "b"),
+                ("x00", Operation::Xor, "a", "z00"),
+            ]),
+        )
+        .expect_err("a and b read each other");
+        assert_eq!(
+            err,
+            CircuitError::Cycle(vec!["a".into(), "b".into(), "a".into()])
+        );
+        assert_eq!(err.to_string(), "Gates form a loop: a -> b -> a");
+    }
+
+    #[test]
+    fn long_chain() {
+        // deeper than recursion on a test thread's stack would go
+        const LENGTH: usize = 200_000;
+        let netlist = (0..LENGTH)
+            .map(|idx| {
+                let input = if idx == 0 {
+                    "x00".to_string()
+                } else {
+                    format!("w{}", idx - 1)
+                };
+                (input, format!("w{}", idx))
+            })
+            .collect::<Vec<_>>();
+        let netlist = netlist
+            .iter()
+            .map(|(input, output)| (input.as_str(), Operation::Or, "y00", output.as_str()))
+            .collect::<Vec<_>>();
+
+        let circuit = Circuit::new(["x00", "y00"], &gates(&netlist)).expect("valid circuit");
+        let mut state = circuit.state();
+        state[circuit.wire("x00").expect("input")] = 0b10;
+        circuit.evaluate(&mut state);
+        assert_eq!(
+            state[circuit.wire(&format!("w{}", LENGTH - 1)).expect("gate")],
+            0b10
+        );
+    }
+
+    #[test]
+    fn buses() {
+        let bus = |inputs: &[&str]| {
+            let circuit = Circuit::new(inputs.iter().copied(), &HashMap::new())?;
+            Ok(circuit
+                .bus('x')?
+                .iter()
+                .map(|wire| circuit.names[*wire].clone())
+                .collect::<Vec<_>>())
+        };
+
+        let wide = (0..11).map(|bit| format!("x{}", bit)).collect::<Vec<_>>();
+        let wide = wide.iter().map(String::as_str).collect::<Vec<_>>();
+        assert_eq!(bus(&wide), Ok(wide.iter().map(|w| w.to_string()).collect()));
+
+        let too_wide = (0..65)
+            .map(|bit| format!("x{:02}", bit))
+            .collect::<Vec<_>>();
+        let too_wide = too_wide.iter().map(String::as_str).collect::<Vec<_>>();
+        assert_eq!(
+            bus(&too_wide),
+            Err(CircuitError::BusTooWide {
+                prefix: 'x',
+                width: 65
+            })
+        );
+        assert_eq!(bus(&too_wide[..64]).map(|wires| wires.len()), Ok(64));
+
+        assert_eq!(
+            bus(&["x0", "x2", "y0"]),
+            Err(CircuitError::MissingBit {
+                prefix: 'x',
+                bit: 1
+            })
+        );
+        assert_eq!(
+            bus(&["x0", "x1", "x01"]),
+            Err(CircuitError::DuplicateBit {
+                prefix: 'x',
+                bit: 1
+            })
+        );
+        assert_eq!(bus(&["y0"]), Ok(vec![]));
+    }
+}
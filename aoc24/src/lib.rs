@@ -1,9 +1,9 @@
-use std::{
-    collections::{HashMap, HashSet},
-    hash::Hash,
-    sync::atomic::{AtomicU32, Ordering},
-};
+use std::collections::{HashMap, HashSet};
 
+use aoc_support::{
+    error::{parse_complete, ProcessingError},
+    Solution,
+};
 use itertools::Itertools;
 use nom::{
     branch::alt,
@@ -14,16 +14,10 @@ use nom::{
     IResult, Parser,
 };
 use nom_supreme::ParserExt;
-use rayon::prelude::*;
 
-#[derive(thiserror::Error, Debug, PartialEq)]
-enum ProcessingError {
-    #[error("Failed to parse using Nom")]
-    NomError(#[source] nom::Err<nom::error::Error<String>>),
+use crate::circuit::{get_lane, set_lane, Circuit, CircuitError};
 
-    #[error("Unparsed data remained: {0:?}")]
-    UnparsedData(String),
-}
+mod circuit;
 
 #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
 enum Operation {
@@ -48,7 +42,7 @@ struct OperationMapping {
 }
 
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     inputs: HashMap<String, bool>, // 0 == false, 1 == true
     gate_map: HashMap<String, OperationMapping>,
 }
@@ -99,125 +93,70 @@ fn parse_gate(s: &str) -> IResult<&str, Gate> {
 }
 
 fn parse_input(s: &str) -> Result<Input, ProcessingError> {
-    let (rest, input) = tuple((
-        separated_list1(line_ending, parse_input_value).terminated(many1(line_ending)),
-        separated_list1(line_ending, parse_gate).terminated(many0(line_ending)),
-    ))
-    .map(|(input_vec, gates)| {
-        let mut inputs = HashMap::new();
-        for (k, v) in input_vec {
-            inputs.insert(k, v);
-        }
-
-        let mut gate_map = HashMap::new();
-        for g in gates.iter() {
-            gate_map.insert(g.output.clone(), g.mapping());
-        }
-
-        Input { inputs, gate_map }
-    })
-    .parse(s)?;
-
-    if !rest.is_empty() {
-        return Err(ProcessingError::UnparsedData(rest.into()));
-    }
-
-    Ok(input)
-}
-
-impl<INNER: Into<String>> From<nom::Err<nom::error::Error<INNER>>> for ProcessingError {
-    fn from(value: nom::Err<nom::error::Error<INNER>>) -> Self {
-        ProcessingError::NomError(value.map_input(|i| i.into()))
-    }
-}
-
-#[tracing::instrument(ret, skip(inputs, gate_map))]
-fn solve(
-    v: &str,
-    inputs: &mut HashMap<String, bool>,
-    gate_map: &HashMap<String, OperationMapping>,
-    max_depth: usize,
-) -> Option<bool> {
-    if max_depth == 0 {
-        return None;
-    }
-    if let Some(value) = inputs.get(v) {
-        return Some(*value);
-    }
-
-    // need to find the underlying value instead
-    match gate_map.get(v) {
-        Some(OperationMapping {
-            op1,
-            op2,
-            operation,
-        }) => {
-            let v1 = solve(op1, inputs, gate_map, max_depth - 1)?;
-            let v2 = solve(op2, inputs, gate_map, max_depth - 1)?;
-            Some(match operation {
-                Operation::And => v1 && v2,
-                Operation::Or => v1 || v2,
-                Operation::Xor => v1 ^ v2,
-            })
-        }
-        None => panic!("Output {} should have has a gate connected to it", v),
-    }
-}
+    parse_complete(
+        tuple((
+            separated_list1(line_ending, parse_input_value).terminated(many1(line_ending)),
+            separated_list1(line_ending, parse_gate).terminated(many0(line_ending)),
+        ))
+        .map(|(input_vec, gates)| {
+            let mut inputs = HashMap::new();
+            for (k, v) in input_vec {
+                inputs.insert(k, v);
+            }
 
-pub fn part1(input: &str) -> color_eyre::Result<usize> {
-    let mut input = parse_input(input)?;
-
-    let z_outs = input
-        .gate_map
-        .keys()
-        .filter(|k| k.starts_with("z"))
-        .sorted()
-        .rev()
-        .cloned()
-        .collect::<Vec<_>>();
-
-    let mut result = 0;
-    for z in z_outs {
-        result <<= 1;
-        match solve(&z, &mut input.inputs, &input.gate_map, 89) {
-            Some(true) => result += 1,
-            Some(false) => {}
-            None => panic!("invalid linkages"),
-        }
-    }
+            let mut gate_map = HashMap::new();
+            for g in gates.iter() {
+                gate_map.insert(g.output.clone(), g.mapping());
+            }
 
-    Ok(result)
+            Input { inputs, gate_map }
+        }),
+        s,
+    )
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone)]
 struct Executer {
-    x_bits: usize,
-    y_bits: usize,
-    z_bits: usize,
+    x: Vec<usize>,
+    y: Vec<usize>,
+    z: Vec<usize>,
+    inputs: Vec<String>,
     gate_map: HashMap<String, OperationMapping>,
+    circuit: Circuit,
 }
 
 impl Executer {
-    fn from(
-        x_bits: usize,
-        y_bits: usize,
-        z_bits: usize,
-        gate_map: &HashMap<String, OperationMapping>,
-    ) -> Self {
-        Self {
-            x_bits,
-            y_bits,
-            z_bits,
-            gate_map: gate_map.clone(),
-        }
+    fn from(input: &Input) -> Result<Self, CircuitError> {
+        let inputs = input.inputs.keys().cloned().collect::<Vec<_>>();
+        let circuit = Circuit::new(inputs.iter().map(String::as_str), &input.gate_map)?;
+
+        Ok(Self {
+            x: circuit.bus('x')?,
+            y: circuit.bus('y')?,
+            z: circuit.bus('z')?,
+            inputs,
+            gate_map: input.gate_map.clone(),
+            circuit,
+        })
     }
 
-    fn swap_outputs(&mut self, g1: &str, g2: &str) {
-        let m1 = self.gate_map.get(g1).unwrap().clone();
-        let m2 = self.gate_map.get(g2).unwrap().clone();
+    /// Swaps the gates driving `g1` and `g2`, which fails if either has
+    /// no gate or the swap creates a loop.
+    #[allow(dead_code)]
+    fn swap_outputs(&mut self, g1: &str, g2: &str) -> Result<(), CircuitError> {
+        let mapping = |wire: &str| {
+            self.gate_map
+                .get(wire)
+                .cloned()
+                .ok_or_else(|| CircuitError::NotAGateOutput(wire.to_string()))
+        };
+        let (m1, m2) = (mapping(g1)?, mapping(g2)?);
 
         self.gate_map.insert(g1.to_string(), m2);
         self.gate_map.insert(g2.to_string(), m1);
+
+        self.circuit = Circuit::new(self.inputs.iter().map(String::as_str), &self.gate_map)?;
+        Ok(())
     }
 
     fn outpus_involved(&self, gate: &String) -> HashSet<String> {
@@ -239,106 +178,130 @@ impl Executer {
         result
     }
 
-    fn exec(&self, x: usize, y: usize) -> Option<usize> {
-        let mut inputs = HashMap::new();
-
-        for id in 0..self.x_bits {
-            let key = format!("x{:02}", id);
-            inputs.insert(key, ((x >> id) & 0x01) != 0);
-        }
-        for id in 0..self.y_bits {
-            let key = format!("y{:02}", id);
-            inputs.insert(key, ((y >> id) & 0x01) != 0);
-        }
+    /// Outputs of the circuit for every `(x, y)` pair, evaluating up to 64
+    /// of them at once.
+    fn exec(&self, pairs: &[(u64, u64)]) -> Vec<u64> {
+        let mut state = self.circuit.state();
+        let mut results = Vec::with_capacity(pairs.len());
 
-        let mut result = 0;
-        for id in (0..self.z_bits).into_iter().rev() {
-            result <<= 1;
-            let key = format!("z{:02}", id);
-            match solve(&key, &mut inputs, &self.gate_map, 89) {
-                Some(true) => result += 1,
-                Some(false) => {}
-                None => return None,
+        for chunk in pairs.chunks(64) {
+            for (lane, (x, y)) in chunk.iter().enumerate() {
+                set_lane(&mut state, &self.x, lane, *x);
+                set_lane(&mut state, &self.y, lane, *y);
             }
+            self.circuit.evaluate(&mut state);
+            results.extend((0..chunk.len()).map(|lane| get_lane(&state, &self.z, lane)));
         }
 
-        Some(result)
+        results
     }
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, PartialEq, PartialOrd)]
 struct SwapDest {
     a: String,
     b: String,
 }
 
-pub fn part2(input: &str) -> color_eyre::Result<String> {
-    let input = parse_input(input)?;
-
-    let x_bits = input.inputs.keys().filter(|k| k.starts_with("x")).count();
-    let y_bits = input.inputs.keys().filter(|k| k.starts_with("y")).count();
-    let z_bits = input.gate_map.keys().filter(|k| k.starts_with("z")).count();
-
-    let mut executer = Executer::from(x_bits, y_bits, z_bits, &input.gate_map);
-
-    // find out where the first error occurs
-    //
-    let mut bad_outputs = HashSet::new();
-    let mut good_outputs = HashSet::new();
-
-    for bit in 1..x_bits {
-        let a = 1 << bit;
-        let b = 1 << (bit - 1);
-
-        // ok IFF both carry and sum are ok
-        let s1 = executer.exec(a, 0).unwrap();
-        let s2 = executer.exec(b, b).unwrap();
-        if (s1 == a) && (s2 == a) {
-            println!("BIT {:2} IS OK", bit);
-            good_outputs.extend(executer.outpus_involved(&format!("z{:02}", bit)));
-        } else {
-            //test_bits.push(bit);
-            println!("BIT {:2} SEEMS BAD", bit);
-            bad_outputs.extend(executer.outpus_involved(&format!("z{:02}", bit)));
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 24;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = String;
+    type Params = ();
+
+    fn parse(input: &str) -> color_eyre::Result<Input> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(input: &Input, _: &()) -> color_eyre::Result<u64> {
+        let circuit = Circuit::new(input.inputs.keys().map(String::as_str), &input.gate_map)?;
+
+        let mut state = circuit.state();
+        for (wire, value) in input.inputs.iter() {
+            state[circuit.wire(wire).expect("inputs are wires")] = *value as u64;
         }
+        circuit.evaluate(&mut state);
+
+        Ok(get_lane(&state, &circuit.bus('z')?, 0))
     }
 
-    let all_outputs = executer.gate_map.keys().cloned().collect::<HashSet<_>>();
+    fn part2(input: &Input, _: &()) -> color_eyre::Result<String> {
+        let executer = Executer::from(input)?;
+        let x_bits = executer.x.len();
+
+        // find out where the first error occurs
+        //
+        let mut bad_outputs = HashSet::new();
+        let mut good_outputs = HashSet::new();
+
+        // a sum and a carry into every bit, all evaluated together
+        let pairs = (1..x_bits)
+            .flat_map(|bit| [(1 << bit, 0), (1 << (bit - 1), 1 << (bit - 1))])
+            .collect::<Vec<_>>();
+        let sums = executer.exec(&pairs);
+
+        for bit in 1..x_bits {
+            let a = 1 << bit;
+
+            // ok IFF both carry and sum are ok
+            let s1 = sums[2 * (bit - 1)];
+            let s2 = sums[2 * (bit - 1) + 1];
+            if (s1 == a) && (s2 == a) {
+                tracing::debug!("BIT {:2} IS OK", bit);
+                good_outputs.extend(executer.outpus_involved(&format!("z{:02}", bit)));
+            } else {
+                //test_bits.push(bit);
+                tracing::debug!("BIT {:2} SEEMS BAD", bit);
+                bad_outputs.extend(executer.outpus_involved(&format!("z{:02}", bit)));
+            }
+        }
+
+        let bad_outputs = bad_outputs
+            .iter()
+            .filter(|x| !x.starts_with('x') && !x.starts_with('y'))
+            .sorted()
+            .collect::<Vec<_>>();
+
+        // SOLUTION values
+        //   bmn,jss,mvb,rds,wss,z08,z18,z23
 
-    let bad_outputs = bad_outputs
-        .iter()
-        .filter(|x| !x.starts_with('x') && !x.starts_with('y'))
-        .sorted()
-        .collect::<Vec<_>>();
+        // try to swap them and see what happens
 
-    // SOLUTION values
-    //   bmn,jss,mvb,rds,wss,z08,z18,z23
+        tracing::info!("BAD OUTPUTS: {}: {:?}", bad_outputs.len(), bad_outputs);
 
-    // try to swap them and see what happens
+        Ok("".to_string())
+    }
+}
 
-    println!("BAD OUTPUTS: {}: {:?}", bad_outputs.len(), bad_outputs);
+pub fn part1(input: &str) -> color_eyre::Result<u64> {
+    Solver::solve1(input, &())
+}
 
-    Ok("".to_string())
+pub fn part2(input: &str) -> color_eyre::Result<String> {
+    Solver::solve2(input, &())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Once;
 
-    static INIT: Once = Once::new();
-    pub fn init_tests() {
-        INIT.call_once(|| {
-            color_eyre::install().unwrap_or(());
-        });
-    }
+    #[test]
+    fn swaps() {
+        let input = parse_input(include_str!("../example.txt")).expect("valid example");
+        let mut executer = Executer::from(&input).expect("valid circuit");
 
-    #[test_log::test]
-    fn test_part1() {
-        init_tests();
+        executer.swap_outputs("z07", "z03").expect("both are gates");
+        assert_eq!(executer.gate_map["z07"].op1, "hwm");
         assert_eq!(
-            part1(include_str!("../example.txt")).expect("success"),
-            2024
+            executer.swap_outputs("z07", "x00"),
+            Err(CircuitError::NotAGateOutput("x00".into()))
         );
     }
+
+    aoc_support::example_tests!(Solver);
 }
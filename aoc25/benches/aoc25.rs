@@ -1,5 +1,10 @@
+use std::sync::LazyLock;
+
 use divan::black_box;
 
+static INPUT: LazyLock<String> =
+    LazyLock::new(|| aoc_support::input::from_inputs_dir(25).expect("benchmark input"));
+
 fn main() {
     // Run registered benchmarks.
     divan::main();
@@ -7,5 +12,5 @@ fn main() {
 
 #[divan::bench]
 fn part1() {
-    aoc25::part1(black_box(include_str!("../input.txt"))).unwrap();
+    aoc25::part1(black_box(INPUT.as_str())).unwrap();
 }
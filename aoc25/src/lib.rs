@@ -1,3 +1,7 @@
+use aoc_support::{
+    error::{parse_complete, ProcessingError},
+    NoAnswer, Solution,
+};
 use itertools::Itertools;
 use nom::{
     branch::alt,
@@ -8,15 +12,6 @@ use nom::{
 };
 use nom_supreme::ParserExt;
 
-#[derive(thiserror::Error, Debug, PartialEq)]
-enum ProcessingError {
-    #[error("Failed to parse using Nom")]
-    NomError(#[source] nom::Err<nom::error::Error<String>>),
-
-    #[error("Unparsed data remained: {0:?}")]
-    UnparsedData(String),
-}
-
 #[derive(Debug, PartialEq, PartialOrd)]
 enum Key {
     Up(Vec<usize>),
@@ -24,7 +19,7 @@ enum Key {
 }
 
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     keys: Vec<Key>,
 }
 
@@ -84,67 +79,67 @@ fn parse_key(s: &str) -> IResult<&str, Key> {
 }
 
 fn parse_input(s: &str) -> Result<Input, ProcessingError> {
-    let (rest, input) = separated_list1(many1(line_ending), parse_key)
-        .terminated(many0(line_ending))
-        .map(|keys| Input { keys })
-        .parse(s)?;
+    parse_complete(
+        separated_list1(many1(line_ending), parse_key)
+            .terminated(many0(line_ending))
+            .map(|keys| Input { keys }),
+        s,
+    )
+}
 
-    if !rest.is_empty() {
-        return Err(ProcessingError::UnparsedData(rest.into()));
-    }
+pub struct Solver;
 
-    Ok(input)
-}
+impl Solution for Solver {
+    const DAY: u8 = 25;
+    const HAS_PART2: bool = false;
 
-impl<INNER: Into<String>> From<nom::Err<nom::error::Error<INNER>>> for ProcessingError {
-    fn from(value: nom::Err<nom::error::Error<INNER>>) -> Self {
-        ProcessingError::NomError(value.map_input(|i| i.into()))
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = NoAnswer;
+    type Params = ();
+
+    fn parse(input: &str) -> color_eyre::Result<Input> {
+        Ok(parse_input(input)?)
     }
-}
 
-pub fn part1(input: &str) -> color_eyre::Result<usize> {
-    let input = parse_input(input)?;
-
-    let ups = input
-        .keys
-        .iter()
-        .filter(|k| matches!(k, Key::Up(_)))
-        .collect_vec();
-    let downs = input
-        .keys
-        .iter()
-        .filter(|k| matches!(k, Key::Down(_)))
-        .collect_vec();
-
-    // cartesian product really
-    let mut cnt = 0;
-    for u in ups.iter() {
-        for d in downs.iter() {
-            if u.fit(d) {
-                println!("FIT: {:?} and {:?}", u, d);
-                cnt += 1;
+    fn part1(input: &Input, _: &()) -> color_eyre::Result<usize> {
+        let ups = input
+            .keys
+            .iter()
+            .filter(|k| matches!(k, Key::Up(_)))
+            .collect_vec();
+        let downs = input
+            .keys
+            .iter()
+            .filter(|k| matches!(k, Key::Down(_)))
+            .collect_vec();
+
+        // cartesian product really
+        let mut cnt = 0;
+        for u in ups.iter() {
+            for d in downs.iter() {
+                if u.fit(d) {
+                    tracing::debug!("FIT: {:?} and {:?}", u, d);
+                    cnt += 1;
+                }
             }
         }
+
+        Ok(cnt)
+    }
+
+    fn part2(_: &Input, _: &()) -> color_eyre::Result<NoAnswer> {
+        Ok(NoAnswer)
     }
+}
 
-    Ok(cnt)
+pub fn part1(input: &str) -> color_eyre::Result<usize> {
+    Solver::solve1(input, &())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Once;
 
-    static INIT: Once = Once::new();
-    pub fn init_tests() {
-        INIT.call_once(|| {
-            color_eyre::install().unwrap_or(());
-        });
-    }
-
-    #[test]
-    fn test_part1() {
-        init_tests();
-        assert_eq!(part1(include_str!("../example.txt")).expect("success"), 3);
-    }
+    aoc_support::example_tests!(Solver);
 }
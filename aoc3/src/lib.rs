@@ -1,5 +1,6 @@
 use std::num::ParseIntError;
 
+use aoc_support::Solution;
 use regex::Captures;
 
 #[derive(PartialEq, Eq, Debug)]
@@ -26,7 +27,7 @@ impl TryFrom<Captures<'_>> for Multiplication {
 }
 
 #[derive(PartialEq, Eq, Debug)]
-struct Input {
+pub struct Input {
     items: Vec<Multiplication>,
 }
 
@@ -48,7 +49,7 @@ impl TryFrom<Captures<'_>> for Instruction {
     }
 }
 
-struct Input2 {
+pub struct Input2 {
     items: Vec<Instruction>,
 }
 
@@ -81,31 +82,55 @@ mod parse {
     }
 }
 
-pub fn part1(s: &str) -> usize {
-    parse::input(s)
-        .items
-        .iter()
-        .map(Multiplication::value)
-        .reduce(|a, b| a + b)
-        .unwrap_or(0)
-}
+pub struct Solver;
 
-pub fn part2(s: &str) -> usize {
-    let mut result = 0;
-    let mut on = true;
-
-    for item in parse::input2(s).items.iter() {
-        match item {
-            Instruction::Do => on = true,
-            Instruction::Dont => on = false,
-            Instruction::Mul(m) => {
-                if on {
-                    result += m.value();
+impl Solution for Solver {
+    const DAY: u8 = 3;
+
+    /// Part 1 only looks at the multiplications, part 2 at all the instructions
+    type Input = (Input, Input2);
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Params = ();
+
+    fn parse(s: &str) -> color_eyre::Result<Self::Input> {
+        Ok((parse::input(s), parse::input2(s)))
+    }
+
+    fn part1((input, _): &Self::Input, _: &()) -> color_eyre::Result<usize> {
+        Ok(input
+            .items
+            .iter()
+            .map(Multiplication::value)
+            .reduce(|a, b| a + b)
+            .unwrap_or(0))
+    }
+
+    fn part2((_, input): &Self::Input, _: &()) -> color_eyre::Result<usize> {
+        let mut result = 0;
+        let mut on = true;
+
+        for item in input.items.iter() {
+            match item {
+                Instruction::Do => on = true,
+                Instruction::Dont => on = false,
+                Instruction::Mul(m) => {
+                    if on {
+                        result += m.value();
+                    }
                 }
             }
         }
+        Ok(result)
     }
-    result
+}
+
+pub fn part1(s: &str) -> usize {
+    Solver::solve1(s, &()).expect("valid input")
+}
+
+pub fn part2(s: &str) -> usize {
+    Solver::solve2(s, &()).expect("valid input")
 }
 
 #[cfg(test)]
@@ -147,13 +172,5 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(include_str!("../example.txt")), 161);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(include_str!("../example.txt")), 48);
-    }
+    aoc_support::example_tests!(Solver);
 }
@@ -1,7 +1,10 @@
 use std::{fmt::Debug, ops::Add};
 
+use aoc_support::Solution;
+use color_eyre::eyre::ensure;
+
 #[derive(Debug, PartialEq, Eq, Clone)]
-struct Matrix {
+pub struct Matrix {
     rows: Vec<Vec<char>>,
 }
 
@@ -80,7 +83,7 @@ impl<'a> Matrix {
         Some(row[p.x as usize])
     }
 
-    fn points(&self) -> PointsIterator {
+    fn points(&self) -> PointsIterator<'_> {
         PointsIterator::new(self)
     }
 
@@ -248,30 +251,49 @@ mod parse {
     }
 }
 
-pub fn part1(input: &str) -> usize {
-    let (r, m) = parse::input_matrix(input).expect("Parsing is ok");
-    assert_eq!(r, "");
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 4;
 
-    // find all that match XMAS
-    let mut count = 0;
-    for start_pos in m.points() {
-        for d in Heading::all() {
-            let items = m.chars_at(start_pos, &d).take(4).collect::<String>();
+    type Input = Matrix;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Params = ();
+
+    fn parse(input: &str) -> color_eyre::Result<Matrix> {
+        let (r, m) = parse::input_matrix(input).map_err(|e| e.to_owned())?;
+        ensure!(r.is_empty(), "Unparsed data remained: {:?}", r);
+        Ok(m)
+    }
 
-            if items == "XMAS" {
-                count += 1;
+    fn part1(m: &Matrix, _: &()) -> color_eyre::Result<usize> {
+        // find all that match XMAS
+        let mut count = 0;
+        for start_pos in m.points() {
+            for d in Heading::all() {
+                let items = m.chars_at(start_pos, &d).take(4).collect::<String>();
+
+                if items == "XMAS" {
+                    count += 1;
+                }
             }
         }
+        Ok(count)
+    }
+
+    fn part2(m: &Matrix, _: &()) -> color_eyre::Result<usize> {
+        // find all that match XMAS
+        Ok(m.points().map(|p| m.xmas_count(&p)).sum())
     }
-    count
 }
 
-pub fn part2(input: &str) -> usize {
-    let (r, m) = parse::input_matrix(input).expect("Parsing is ok");
-    assert_eq!(r, "");
+pub fn part1(input: &str) -> usize {
+    Solver::solve1(input, &()).expect("Parsing is ok")
+}
 
-    // find all that match XMAS
-    m.points().map(|p| m.xmas_count(&p)).sum()
+pub fn part2(input: &str) -> usize {
+    Solver::solve2(input, &()).expect("Parsing is ok")
 }
 
 #[cfg(test)]
@@ -306,11 +328,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(include_str!("../example.txt")), 18);
-    }
-
     #[test]
     fn test_xmas() {
         let (r, m) = super::parse::input_matrix("M.S\n.A.\nM.S").expect("parse works");
@@ -324,8 +341,5 @@ mod tests {
         assert_eq!(m.xmas_count(&Point { x: 0, y: 0 }), 0);
     }
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(include_str!("../example.txt")), 9);
-    }
+    aoc_support::example_tests!(Solver);
 }
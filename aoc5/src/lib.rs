@@ -1,5 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
+use aoc_support::Solution;
+use color_eyre::eyre::ensure;
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 struct Ordering {
     before: u32,
@@ -7,7 +10,7 @@ struct Ordering {
 }
 
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     ordering: Vec<Ordering>,
     lines: Vec<Vec<u32>>,
 }
@@ -101,45 +104,65 @@ fn fix_priority(data: &[u32], before_to_after: &HashMap<u32, HashSet<u32>>) -> V
     result
 }
 
-pub fn part1(input: &str) -> u32 {
-    let (r, input) = parse::parse(input).expect("valid input");
-    assert!(r.is_empty());
-
-    let mut before_to_after = HashMap::new();
-    for o in input.ordering {
+impl Input {
+    fn before_to_after(&self) -> HashMap<u32, HashSet<u32>> {
+        let mut before_to_after = HashMap::new();
+        for o in self.ordering.iter() {
+            before_to_after
+                .entry(o.before)
+                .or_insert_with(HashSet::new)
+                .insert(o.after);
+        }
         before_to_after
-            .entry(o.before)
-            .or_insert_with(HashSet::new)
-            .insert(o.after);
     }
-
-    input
-        .lines
-        .iter()
-        .filter(|v| is_priority_respected(v, &before_to_after))
-        .map(MidValued::mid_value)
-        .sum()
 }
 
-pub fn part2(input: &str) -> u32 {
-    let (r, input) = parse::parse(input).expect("valid input");
-    assert!(r.is_empty());
+pub struct Solver;
 
-    let mut before_to_after = HashMap::new();
-    for o in input.ordering {
-        before_to_after
-            .entry(o.before)
-            .or_insert_with(HashSet::new)
-            .insert(o.after);
+impl Solution for Solver {
+    const DAY: u8 = 5;
+
+    type Input = Input;
+    type Answer1 = u32;
+    type Answer2 = u32;
+    type Params = ();
+
+    fn parse(input: &str) -> color_eyre::Result<Input> {
+        let (r, input) = parse::parse(input).map_err(|e| e.to_owned())?;
+        ensure!(r.is_empty(), "Unparsed data remained: {:?}", r);
+        Ok(input)
     }
 
-    input
-        .lines
-        .iter()
-        .filter(|v| !is_priority_respected(v, &before_to_after))
-        .map(|v| fix_priority(v, &before_to_after))
-        .map(|v| MidValued::mid_value(&v))
-        .sum()
+    fn part1(input: &Input, _: &()) -> color_eyre::Result<u32> {
+        let before_to_after = input.before_to_after();
+
+        Ok(input
+            .lines
+            .iter()
+            .filter(|v| is_priority_respected(v, &before_to_after))
+            .map(MidValued::mid_value)
+            .sum())
+    }
+
+    fn part2(input: &Input, _: &()) -> color_eyre::Result<u32> {
+        let before_to_after = input.before_to_after();
+
+        Ok(input
+            .lines
+            .iter()
+            .filter(|v| !is_priority_respected(v, &before_to_after))
+            .map(|v| fix_priority(v, &before_to_after))
+            .map(|v| MidValued::mid_value(&v))
+            .sum())
+    }
+}
+
+pub fn part1(input: &str) -> u32 {
+    Solver::solve1(input, &()).expect("valid input")
+}
+
+pub fn part2(input: &str) -> u32 {
+    Solver::solve2(input, &()).expect("valid input")
 }
 
 #[cfg(test)]
@@ -153,14 +176,5 @@ mod tests {
         assert_eq!(vec![1, 2, 3, 4, 5].mid_value(), 3);
     }
 
-    #[test]
-    #[tracing_test::traced_test]
-    fn test_part1() {
-        assert_eq!(part1(include_str!("../example.txt")), 143);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(include_str!("../example.txt")), 123);
-    }
+    aoc_support::example_tests!(Solver);
 }
@@ -1,5 +1,7 @@
 use std::{collections::HashSet, ops::Add};
 
+use aoc_support::Solution;
+use color_eyre::eyre::ensure;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Hash, Copy, Clone)]
@@ -74,7 +76,7 @@ impl Heading {
 }
 
 #[derive(Debug, PartialEq, Eq, Default, Clone)]
-struct Lab {
+pub struct Lab {
     walls: HashSet<(i32, i32)>, // (row,column) where a `#` exists
     rows: i32,
     cols: i32,
@@ -226,39 +228,59 @@ pub(crate) fn find_size(lab: &Lab) -> (HashSet<Point>, bool) {
     (visited, true)
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 6;
+
+    type Input = Lab;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Params = ();
+
+    fn parse(input: &str) -> color_eyre::Result<Lab> {
+        let (r, lab) = parse::input(input).map_err(|e| e.to_owned())?;
+        ensure!(r.is_empty(), "Unparsed data remained: {:?}", r);
+        Ok(lab)
+    }
+
+    fn part1(lab: &Lab, _: &()) -> color_eyre::Result<usize> {
+        let (visited, _) = find_size(lab);
+        Ok(visited.len())
+    }
+
+    fn part2(lab: &Lab, _: &()) -> color_eyre::Result<usize> {
+        let (initial_visisted, _) = find_size(lab);
+
+        // try to place an obstacle in all visisted places and see if we go into some loop.
+        // Obstacle only makes sense in visisted (otherwise we do not block any path really)
+        Ok(initial_visisted
+            .par_iter()
+            .map(|point| {
+                if point == &lab.start.into() {
+                    return 0;
+                }
+
+                let mut changed_lab = lab.clone();
+                changed_lab.walls.insert((*point).into());
+
+                // check if now we loop
+                if let (_, true) = find_size(&changed_lab) {
+                    1
+                } else {
+                    0
+                }
+            })
+            .sum())
+    }
+}
+
 pub fn part1(input: &str) -> usize {
-    let (r, lab) = parse::input(input).expect("valid input");
-    assert!(r.is_empty());
-    let (visited, _) = find_size(&lab);
-    visited.len()
+    Solver::solve1(input, &()).expect("valid input")
 }
 
 pub fn part2(input: &str) -> usize {
-    let (r, lab) = parse::input(input).expect("valid input");
-    assert!(r.is_empty());
-
-    let (initial_visisted, _) = find_size(&lab);
-
-    // try to place an obstacle in all visisted places and see if we go into some loop.
-    // Obstacle only makes sense in visisted (otherwise we do not block any path really)
-    initial_visisted
-        .par_iter()
-        .map(|point| {
-            if point == &lab.start.into() {
-                return 0;
-            }
-
-            let mut changed_lab = lab.clone();
-            changed_lab.walls.insert((*point).into());
-
-            // check if now we loop
-            if let (_, true) = find_size(&changed_lab) {
-                1
-            } else {
-                0
-            }
-        })
-        .sum()
+    Solver::solve2(input, &()).expect("valid input")
 }
 
 #[cfg(test)]
@@ -326,14 +348,5 @@ mod tests {
         );
     }
 
-    #[test]
-    #[tracing_test::traced_test]
-    fn test_part1() {
-        assert_eq!(part1(include_str!("../example.txt")), 41);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(include_str!("../example.txt")), 6);
-    }
+    aoc_support::example_tests!(Solver);
 }
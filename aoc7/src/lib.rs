@@ -1,8 +1,10 @@
+use aoc_support::Solution;
+use color_eyre::eyre::ensure;
 use rayon::prelude::*;
 use std::collections::HashSet;
 
 #[derive(Debug, PartialEq, PartialOrd, Eq, Ord)]
-struct Equation {
+pub struct Equation {
     target: u64,
     parts: Vec<u64>,
 }
@@ -69,7 +71,7 @@ fn substract(target: u64, x: u64) -> Option<u64> {
 }
 
 fn divide(target: u64, x: u64) -> Option<u64> {
-    if target % x != 0 {
+    if !target.is_multiple_of(x) {
         return None;
     }
     Some(target / x)
@@ -87,50 +89,61 @@ fn remove_suffix(target: u64, x: u64) -> Option<u64> {
     Some(ts.parse().expect("valid number"))
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 7;
+
+    type Input = Vec<Equation>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+    type Params = ();
+
+    fn parse(input: &str) -> color_eyre::Result<Vec<Equation>> {
+        let (r, equations) = parse::equations(input).map_err(|e| e.to_owned())?;
+        ensure!(r.is_empty(), "Unparsed data remained: {:?}", r);
+        Ok(equations)
+    }
+
+    fn part1(equations: &Vec<Equation>, _: &()) -> color_eyre::Result<u64> {
+        Ok(equations
+            .par_iter()
+            .filter(|e| {
+                e.solvable_by(&[
+                    &(substract as fn(u64, u64) -> Option<u64>),
+                    &(divide as fn(u64, u64) -> Option<u64>),
+                ])
+            })
+            .map(|e| e.target)
+            .sum())
+    }
+
+    fn part2(equations: &Vec<Equation>, _: &()) -> color_eyre::Result<u64> {
+        Ok(equations
+            .par_iter()
+            .filter(|e| {
+                e.solvable_by(&[
+                    &(substract as fn(u64, u64) -> Option<u64>),
+                    &(divide as fn(u64, u64) -> Option<u64>),
+                    &(remove_suffix as fn(u64, u64) -> Option<u64>),
+                ])
+            })
+            .map(|e| e.target)
+            .sum())
+    }
+}
+
 pub fn part1(input: &str) -> u64 {
-    let (r, equations) = parse::equations(input).expect("valid input");
-    assert!(r.is_empty());
-
-    equations
-        .par_iter()
-        .filter(|e| {
-            e.solvable_by(&[
-                &(substract as fn(u64, u64) -> Option<u64>),
-                &(divide as fn(u64, u64) -> Option<u64>),
-            ])
-        })
-        .map(|e| e.target)
-        .sum()
+    Solver::solve1(input, &()).expect("valid input")
 }
 
 pub fn part2(input: &str) -> u64 {
-    let (r, equations) = parse::equations(input).expect("valid input");
-    assert!(r.is_empty());
-
-    equations
-        .par_iter()
-        .filter(|e| {
-            e.solvable_by(&[
-                &(substract as fn(u64, u64) -> Option<u64>),
-                &(divide as fn(u64, u64) -> Option<u64>),
-                &(remove_suffix as fn(u64, u64) -> Option<u64>),
-            ])
-        })
-        .map(|e| e.target)
-        .sum()
+    Solver::solve2(input, &()).expect("valid input")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(include_str!("../example.txt")), 3749);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(include_str!("../example.txt")), 11387);
-    }
+    aoc_support::example_tests!(Solver);
 }
@@ -1,3 +1,5 @@
+use aoc_support::Solution;
+use color_eyre::eyre::ensure;
 use glam::IVec2;
 use itertools::Itertools;
 use std::{
@@ -6,7 +8,7 @@ use std::{
 };
 
 #[derive(Default, Debug)]
-struct Map {
+pub struct Map {
     rows: i32,
     cols: i32,
     antennas: HashMap<char, HashSet<IVec2>>, // col, row (aka (x, y))
@@ -78,78 +80,87 @@ pub mod parsing {
     }
 }
 
-pub fn part1(input: &str) -> usize {
-    let (r, map) = parsing::map(input).expect("valid input");
-    assert!(r.is_empty());
-
-    let mut antinodes = HashSet::new();
-
-    // find all possible combinations of antinotes
-
-    map.antennas.iter().for_each(|(_, positions)| {
-        // have to combine every position with every other position.
-        positions.iter().tuple_combinations().for_each(|(p1, p2)| {
-            if p1 == p2 {
-                return;
-            }
-            let p = *p1 + *p1 - *p2;
-            if map.contains(p) {
-                antinodes.insert(p);
-            }
-
-            let p = *p2 + *p2 - *p1;
-            if map.contains(p) {
-                antinodes.insert(p);
-            }
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 8;
+
+    type Input = Map;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Params = ();
+
+    fn parse(input: &str) -> color_eyre::Result<Map> {
+        let (r, map) = parsing::map(input).map_err(|e| e.to_owned())?;
+        ensure!(r.is_empty(), "Unparsed data remained: {:?}", r);
+        Ok(map)
+    }
+
+    fn part1(map: &Map, _: &()) -> color_eyre::Result<usize> {
+        let mut antinodes = HashSet::new();
+
+        // find all possible combinations of antinotes
+
+        map.antennas.iter().for_each(|(_, positions)| {
+            // have to combine every position with every other position.
+            positions.iter().tuple_combinations().for_each(|(p1, p2)| {
+                if p1 == p2 {
+                    return;
+                }
+                let p = *p1 + *p1 - *p2;
+                if map.contains(p) {
+                    antinodes.insert(p);
+                }
+
+                let p = *p2 + *p2 - *p1;
+                if map.contains(p) {
+                    antinodes.insert(p);
+                }
+            });
+        });
+
+        Ok(antinodes.len())
+    }
+
+    fn part2(map: &Map, _: &()) -> color_eyre::Result<usize> {
+        let mut antinodes = HashSet::new();
+
+        map.antennas.iter().for_each(|(_, positions)| {
+            // have to combine every position with every other position.
+            positions.iter().tuple_combinations().for_each(|(p1, p2)| {
+                if p1 == p2 {
+                    return;
+                }
+
+                let d = p1 - p2;
+                antinodes.extend(
+                    successors(Some(*p1), |v| {
+                        let p = *v + d;
+                        map.contains(p).then_some(p)
+                    })
+                    .chain(successors(Some(*p2), |v| {
+                        let p = *v - d;
+                        map.contains(p).then_some(p)
+                    })),
+                );
+            })
         });
-    });
 
-    antinodes.len()
+        Ok(antinodes.len())
+    }
+}
+
+pub fn part1(input: &str) -> usize {
+    Solver::solve1(input, &()).expect("valid input")
 }
 
 pub fn part2(input: &str) -> usize {
-    let (r, map) = parsing::map(input).expect("valid input");
-    assert!(r.is_empty());
-
-    let mut antinodes = HashSet::new();
-
-    map.antennas.iter().for_each(|(_, positions)| {
-        // have to combine every position with every other position.
-        positions.iter().tuple_combinations().for_each(|(p1, p2)| {
-            if p1 == p2 {
-                return;
-            }
-
-            let d = p1 - p2;
-            antinodes.extend(
-                successors(Some(*p1), |v| {
-                    let p = *v + d;
-                    map.contains(p).then_some(p)
-                })
-                .chain(successors(Some(*p2), |v| {
-                    let p = *v - d;
-                    map.contains(p).then_some(p)
-                })),
-            );
-        })
-    });
-
-    antinodes.len()
+    Solver::solve2(input, &()).expect("valid input")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    #[tracing_test::traced_test]
-    fn test_part1() {
-        assert_eq!(part1(include_str!("../example.txt")), 14);
-    }
-
-    #[test]
-    #[tracing_test::traced_test]
-    fn test_part2() {
-        assert_eq!(part2(include_str!("../example.txt")), 34);
-    }
+    aoc_support::example_tests!(Solver);
 }
@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
 
+use aoc_support::Solution;
+use color_eyre::eyre::ensure;
 use rayon::prelude::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -15,7 +17,7 @@ struct FlatDiskIterator {
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct FlatDisk {
+pub struct FlatDisk {
     locations: Vec<DiskLocation>,
 }
 
@@ -74,7 +76,7 @@ impl Iterator for FlatDiskIterator {
 }
 
 impl FlatDisk {
-    pub fn allocated(&self) -> usize {
+    fn allocated(&self) -> usize {
         self.locations
             .iter()
             .map(|l| match l {
@@ -84,7 +86,7 @@ impl FlatDisk {
             .sum()
     }
 
-    pub fn blocks(&self) -> FlatDiskIterator {
+    fn blocks(&self) -> FlatDiskIterator {
         FlatDiskIterator::new(self.locations.clone())
     }
 }
@@ -130,52 +132,6 @@ mod parsing {
     }
 }
 
-pub fn part1(input: &str) -> usize {
-    let (r, d) = parsing::flat_disk(input).expect("valid input");
-    assert!(r.is_empty());
-
-    tracing::info!("Allocated: {}", d.allocated());
-
-    // File IDs only
-    let mut blocks = Vec::new();
-    let mut id = 0;
-    for f in &d.locations {
-        if let DiskLocation::File(length) = f {
-            for _ in 0..*length {
-                blocks.push(id);
-            }
-            id += 1
-        }
-    }
-
-    tracing::debug!("BLOCKS: {:?}", blocks);
-
-    let mut fwd = blocks.iter();
-    let mut bwd = blocks.iter().rev();
-
-    tracing::debug!("DATA:");
-
-    tracing::debug!(
-        "RESULT:  {:?}",
-        d.blocks()
-            .map(|b| match b {
-                BlockType::Occupied => "X",
-                BlockType::Free => ".",
-            })
-            .collect::<Vec<_>>()
-    );
-
-    (0..d.allocated())
-        .zip(d.blocks())
-        .map(|(idx, b)| {
-            idx * match b {
-                BlockType::Occupied => fwd.next().expect("has value"),
-                BlockType::Free => bwd.next().expect("has value"),
-            }
-        })
-        .sum()
-}
-
 // Content for disk: free or file with index
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum BlockContent {
@@ -183,91 +139,155 @@ enum BlockContent {
     Free,
 }
 
-pub fn part2(input: &str) -> usize {
-    // TODO: implement
-    let (r, d) = parsing::flat_disk(input).expect("valid input");
-    assert!(r.is_empty());
-
-    tracing::info!("Allocated: {}", d.allocated());
-
-    // File IDs only
-    let mut blocks = Vec::new();
-    let mut id = 0;
-    for f in &d.locations {
-        match f {
-            DiskLocation::File(n) => {
-                for _ in 0..*n {
-                    blocks.push(BlockContent::File(id));
-                }
-                id += 1;
-            }
-            DiskLocation::Free(n) => {
-                for _ in 0..*n {
-                    blocks.push(BlockContent::Free);
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 9;
+
+    type Input = FlatDisk;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Params = ();
+
+    fn parse(input: &str) -> color_eyre::Result<FlatDisk> {
+        let (r, d) = parsing::flat_disk(input).map_err(|e| e.to_owned())?;
+        ensure!(r.is_empty(), "Unparsed data remained: {:?}", r);
+        Ok(d)
+    }
+
+    fn part1(d: &FlatDisk, _: &()) -> color_eyre::Result<usize> {
+        tracing::info!("Allocated: {}", d.allocated());
+
+        // File IDs only
+        let mut blocks = Vec::new();
+        let mut id = 0;
+        for f in &d.locations {
+            if let DiskLocation::File(length) = f {
+                for _ in 0..*length {
+                    blocks.push(id);
                 }
+                id += 1
             }
         }
-    }
 
-    tracing::info!("BLOCKS: {:?}", blocks);
+        tracing::debug!("BLOCKS: {:?}", blocks);
 
-    for end_id in (0..id).rev() {
-        // TRY TO MOVE THE FILE WITH THE GIVEN ID:
-        let start_idx = blocks
-            .iter()
-            .enumerate()
-            .find(|(_, value)| **value == BlockContent::File(end_id))
-            .expect("Files exist")
-            .0;
-
-        let mut file_size = 1;
-        while start_idx + file_size < blocks.len()
-            && blocks.get(start_idx + file_size) == Some(&BlockContent::File(end_id))
-        {
-            file_size += 1;
-        }
+        let mut fwd = blocks.iter();
+        let mut bwd = blocks.iter().rev();
+
+        tracing::debug!("DATA:");
 
-        tracing::info!(
-            "  {:?} starts at {:?} and has length {:?}",
-            end_id,
-            start_idx,
-            file_size
+        tracing::debug!(
+            "RESULT:  {:?}",
+            d.blocks()
+                .map(|b| match b {
+                    BlockType::Occupied => "X",
+                    BlockType::Free => ".",
+                })
+                .collect::<Vec<_>>()
         );
 
-        // find a free space of at least len items
-        let mut free_pos = None;
-        for (idx, window) in blocks.windows(file_size).enumerate() {
-            if window.iter().all(|b| *b == BlockContent::Free) {
-                tracing::info!("FOUND FREE");
-                free_pos = Some(idx);
-                break;
-            }
-            if idx >= start_idx {
-                tracing::info!("REACHED THE END");
-                break;
+        Ok((0..d.allocated())
+            .zip(d.blocks())
+            .map(|(idx, b)| {
+                idx * match b {
+                    BlockType::Occupied => fwd.next().expect("has value"),
+                    BlockType::Free => bwd.next().expect("has value"),
+                }
+            })
+            .sum())
+    }
+
+    fn part2(d: &FlatDisk, _: &()) -> color_eyre::Result<usize> {
+        tracing::info!("Allocated: {}", d.allocated());
+
+        // File IDs only
+        let mut blocks = Vec::new();
+        let mut id = 0;
+        for f in &d.locations {
+            match f {
+                DiskLocation::File(n) => {
+                    for _ in 0..*n {
+                        blocks.push(BlockContent::File(id));
+                    }
+                    id += 1;
+                }
+                DiskLocation::Free(n) => {
+                    for _ in 0..*n {
+                        blocks.push(BlockContent::Free);
+                    }
+                }
             }
         }
-        if let Some(pos) = free_pos {
-            tracing::info!("SUITABLE SPACE: {:?}", pos);
 
-            for n in 0..file_size {
-                blocks[pos + n] = blocks[start_idx + n];
-                blocks[start_idx + n] = BlockContent::Free;
+        tracing::info!("BLOCKS: {:?}", blocks);
+
+        for end_id in (0..id).rev() {
+            // TRY TO MOVE THE FILE WITH THE GIVEN ID:
+            let start_idx = blocks
+                .iter()
+                .enumerate()
+                .find(|(_, value)| **value == BlockContent::File(end_id))
+                .expect("Files exist")
+                .0;
+
+            let mut file_size = 1;
+            while start_idx + file_size < blocks.len()
+                && blocks.get(start_idx + file_size) == Some(&BlockContent::File(end_id))
+            {
+                file_size += 1;
+            }
+
+            tracing::info!(
+                "  {:?} starts at {:?} and has length {:?}",
+                end_id,
+                start_idx,
+                file_size
+            );
+
+            // find a free space of at least len items
+            let mut free_pos = None;
+            for (idx, window) in blocks.windows(file_size).enumerate() {
+                if window.iter().all(|b| *b == BlockContent::Free) {
+                    tracing::info!("FOUND FREE");
+                    free_pos = Some(idx);
+                    break;
+                }
+                if idx >= start_idx {
+                    tracing::info!("REACHED THE END");
+                    break;
+                }
+            }
+            if let Some(pos) = free_pos {
+                tracing::info!("SUITABLE SPACE: {:?}", pos);
+
+                for n in 0..file_size {
+                    blocks[pos + n] = blocks[start_idx + n];
+                    blocks[start_idx + n] = BlockContent::Free;
+                }
+                tracing::info!("BLOCKS: {:?}", blocks);
+            } else {
+                tracing::info!("Cannot move");
             }
-            tracing::info!("BLOCKS: {:?}", blocks);
-        } else {
-            tracing::info!("Cannot move");
         }
+
+        Ok(blocks
+            .par_iter()
+            .enumerate()
+            .map(|(idx, b)| match b {
+                BlockContent::File(n) => n * idx,
+                BlockContent::Free => 0,
+            })
+            .sum())
     }
+}
 
-    blocks
-        .par_iter()
-        .enumerate()
-        .map(|(idx, b)| match b {
-            BlockContent::File(n) => n * idx,
-            BlockContent::Free => 0,
-        })
-        .sum()
+pub fn part1(input: &str) -> usize {
+    Solver::solve1(input, &()).expect("valid input")
+}
+
+pub fn part2(input: &str) -> usize {
+    Solver::solve2(input, &()).expect("valid input")
 }
 
 #[cfg(test)]
@@ -290,15 +310,5 @@ mod tests {
         );
     }
 
-    #[test]
-    #[tracing_test::traced_test]
-    fn test_part1() {
-        assert_eq!(part1(include_str!("../example.txt")), 1928);
-    }
-
-    #[test]
-    #[tracing_test::traced_test]
-    fn test_part2() {
-        assert_eq!(part2(include_str!("../example.txt")), 2858);
-    }
+    aoc_support::example_tests!(Solver);
 }
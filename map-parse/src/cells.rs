@@ -0,0 +1,216 @@
+//! [Parseable] implementations for the most common kinds of map cells.
+//!
+//! Plain values (`char` and integers) parse as themselves. Cells that are
+//! stored as a different type use a separate parser with [Map::parse_as]:
+//!
+//! ```
+//! use map_parse::{Digit, Map, Wall};
+//!
+//! let (_, heights) = Map::<u8>::parse_as::<Digit>("012\n345").unwrap();
+//! let (_, walls) = Map::<bool>::parse_as::<Wall>("#.\n.#").unwrap();
+//! ```
+//!
+//! Enums with one glyph per variant can use [glyph_enum].
+//!
+//! [Map::parse_as]: crate::Map::parse_as
+//! [glyph_enum]: crate::glyph_enum
+
+use nom::{
+    character::complete::{self, satisfy, space0},
+    IResult, Parser,
+};
+use nom_supreme::ParserExt;
+
+use crate::Parseable;
+
+/// Any single character except line endings.
+impl Parseable for char {
+    type Item = char;
+
+    fn parse(s: &str) -> IResult<&str, Self::Item> {
+        satisfy(|c| c != '\r' && c != '\n').parse(s)
+    }
+}
+
+/// Integers separated (and optionally surrounded) by spaces or tabs.
+macro_rules! parseable_integers {
+    ($($int:ident),*) => {
+        $(
+            impl Parseable for $int {
+                type Item = $int;
+
+                fn parse(s: &str) -> IResult<&str, Self::Item> {
+                    complete::$int.preceded_by(space0).terminated(space0).parse(s)
+                }
+            }
+        )*
+    };
+}
+
+parseable_integers!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+/// Parses a single decimal digit `0`-`9` into its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Digit;
+
+impl Parseable for Digit {
+    type Item = u8;
+
+    fn parse(s: &str) -> IResult<&str, Self::Item> {
+        satisfy(|c| c.is_ascii_digit())
+            .map(|c| c as u8 - b'0')
+            .parse(s)
+    }
+}
+
+/// Parses any single character into `true` if it is `GLYPH` (a wall) and
+/// `false` otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wall<const GLYPH: char = '#'>;
+
+impl<const GLYPH: char> Parseable for Wall<GLYPH> {
+    type Item = bool;
+
+    fn parse(s: &str) -> IResult<&str, Self::Item> {
+        char::parse.map(|c| c == GLYPH).parse(s)
+    }
+}
+
+/// Declares an enum whose variants are each represented by a single glyph,
+/// implementing [Parseable] and [ToGlyph](crate::ToGlyph) for it.
+///
+/// ```
+/// use map_parse::{glyph_enum, Map, ToGlyph};
+///
+/// glyph_enum! {
+///     #[derive(Debug, PartialEq, Clone, Copy)]
+///     pub enum Cell {
+///         Wall = '#',
+///         Empty = '.',
+///     }
+/// }
+///
+/// let (_, m) = Map::<Cell>::parse("#.\n.#").unwrap();
+/// assert_eq!(m.get(&glam::IVec2::new(1, 0)), Some(&Cell::Empty));
+/// assert_eq!(Cell::Wall.glyph(), '#');
+/// ```
+#[macro_export]
+macro_rules! glyph_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $glyph:literal),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($(#[$variant_meta])* $variant),*
+        }
+
+        impl $crate::Parseable for $name {
+            type Item = $name;
+
+            fn parse(s: &str) -> $crate::nom::IResult<&str, Self::Item> {
+                let mut chars = s.chars();
+                match chars.next() {
+                    $(Some($glyph) => Ok((chars.as_str(), $name::$variant)),)*
+                    _ => Err($crate::nom::Err::Error($crate::nom::error::Error::new(
+                        s,
+                        $crate::nom::error::ErrorKind::Char,
+                    ))),
+                }
+            }
+        }
+
+        impl $crate::ToGlyph for $name {
+            fn glyph(&self) -> char {
+                match self {
+                    $($name::$variant => $glyph,)*
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Digit, Wall};
+    use crate::{Map, MapParseError, ToGlyph};
+
+    glyph_enum! {
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        enum Cell {
+            Wall = '#',
+            Empty = '.',
+            Box = 'O',
+        }
+    }
+
+    #[test]
+    fn chars_and_digits() {
+        assert_eq!(
+            Map::<char>::parse("a#\r\n.1").expect("valid").1,
+            Map::create(2, 2, vec!['a', '#', '.', '1'])
+        );
+        assert_eq!(
+            Map::<u8>::try_parse_as::<Digit>("09\n57").expect("valid").1,
+            Map::create(2, 2, vec![0, 9, 5, 7])
+        );
+        assert!(matches!(
+            Map::<u8>::try_parse_as::<Digit>("09\n5."),
+            Err(MapParseError::UnknownGlyph {
+                glyph: '.',
+                row: 1,
+                col: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn walls() {
+        assert_eq!(
+            Map::<bool>::try_parse_as::<Wall>("#.\n.#")
+                .expect("valid")
+                .1,
+            Map::create(2, 2, vec![true, false, false, true])
+        );
+        assert_eq!(
+            Map::<bool>::try_parse_as::<Wall<'X'>>("#X")
+                .expect("valid")
+                .1,
+            Map::create(1, 2, vec![false, true])
+        );
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(
+            Map::<i32>::try_parse("  1 -2\n 10\t20 \n")
+                .expect("valid")
+                .1,
+            Map::create(2, 2, vec![1, -2, 10, 20])
+        );
+        assert_eq!(
+            Map::<u64>::parse("12345678901 2").expect("valid").1,
+            Map::create(1, 2, vec![12345678901, 2])
+        );
+    }
+
+    #[test]
+    fn glyph_enums() {
+        let (rest, m) = Map::<Cell>::try_parse("#.O\n").expect("valid");
+
+        assert_eq!(rest, "");
+        assert_eq!(
+            m,
+            Map::create(1, 3, vec![Cell::Wall, Cell::Empty, Cell::Box])
+        );
+        assert_eq!(m.render(), "#.O\n");
+        assert_eq!(Cell::Box.glyph(), 'O');
+        assert!(matches!(
+            Map::<Cell>::try_parse("#x"),
+            Err(MapParseError::UnknownGlyph { glyph: 'x', .. })
+        ));
+    }
+}
@@ -0,0 +1,149 @@
+use glam::IVec2;
+
+/// One of the 4 axis-aligned directions on a map.
+///
+/// Maps grow downwards, so `N` decreases `y`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub enum Direction {
+    N,
+    E,
+    S,
+    W,
+}
+
+impl Direction {
+    /// All directions, clockwise starting from North.
+    pub const ALL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+
+    pub const fn vec(&self) -> IVec2 {
+        match self {
+            Direction::N => IVec2::new(0, -1),
+            Direction::E => IVec2::new(1, 0),
+            Direction::S => IVec2::new(0, 1),
+            Direction::W => IVec2::new(-1, 0),
+        }
+    }
+
+    pub fn turn_left(&self) -> Direction {
+        match self {
+            Direction::N => Direction::W,
+            Direction::E => Direction::N,
+            Direction::S => Direction::E,
+            Direction::W => Direction::S,
+        }
+    }
+
+    pub fn turn_right(&self) -> Direction {
+        match self {
+            Direction::N => Direction::E,
+            Direction::E => Direction::S,
+            Direction::S => Direction::W,
+            Direction::W => Direction::N,
+        }
+    }
+
+    pub fn flip(&self) -> Direction {
+        match self {
+            Direction::N => Direction::S,
+            Direction::E => Direction::W,
+            Direction::S => Direction::N,
+            Direction::W => Direction::E,
+        }
+    }
+}
+
+/// One of the 8 directions on a map, including diagonals.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting from North.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::N,
+        Direction8::NE,
+        Direction8::E,
+        Direction8::SE,
+        Direction8::S,
+        Direction8::SW,
+        Direction8::W,
+        Direction8::NW,
+    ];
+
+    pub const fn vec(&self) -> IVec2 {
+        match self {
+            Direction8::N => IVec2::new(0, -1),
+            Direction8::NE => IVec2::new(1, -1),
+            Direction8::E => IVec2::new(1, 0),
+            Direction8::SE => IVec2::new(1, 1),
+            Direction8::S => IVec2::new(0, 1),
+            Direction8::SW => IVec2::new(-1, 1),
+            Direction8::W => IVec2::new(-1, 0),
+            Direction8::NW => IVec2::new(-1, -1),
+        }
+    }
+
+    fn index(&self) -> usize {
+        Direction8::ALL
+            .iter()
+            .position(|d| d == self)
+            .expect("all directions are listed")
+    }
+
+    /// Turns 45 degrees counter-clockwise.
+    pub fn turn_left(&self) -> Direction8 {
+        Direction8::ALL[(self.index() + 7) % 8]
+    }
+
+    /// Turns 45 degrees clockwise.
+    pub fn turn_right(&self) -> Direction8 {
+        Direction8::ALL[(self.index() + 1) % 8]
+    }
+
+    pub fn flip(&self) -> Direction8 {
+        Direction8::ALL[(self.index() + 4) % 8]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        match value {
+            Direction::N => Direction8::N,
+            Direction::E => Direction8::E,
+            Direction::S => Direction8::S,
+            Direction::W => Direction8::W,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_right(), d.flip());
+            assert_eq!(d.flip().vec(), -d.vec());
+            assert_eq!(Direction8::from(d).flip(), Direction8::from(d.flip()));
+        }
+
+        for d in Direction8::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.flip().vec(), -d.vec());
+        }
+
+        assert_eq!(Direction::N.turn_right(), Direction::E);
+        assert_eq!(Direction8::N.turn_right(), Direction8::NE);
+        assert_eq!(Direction8::N.turn_left(), Direction8::NW);
+    }
+}
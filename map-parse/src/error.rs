@@ -0,0 +1,53 @@
+use miette::SourceSpan;
+
+/// Reasons why a map could not be parsed.
+///
+/// Rows and columns are 0-based, matching map positions.
+#[derive(thiserror::Error, miette::Diagnostic, Debug, PartialEq)]
+pub enum MapParseError {
+    #[error("Row {row} has {found} cells, expected {expected} (at column {col})")]
+    RaggedRow {
+        row: usize,
+        /// The first column past the shorter of `found` and `expected`
+        col: usize,
+        found: usize,
+        expected: usize,
+        #[source_code]
+        input: String,
+        #[label("row length differs from the first row")]
+        span: SourceSpan,
+    },
+
+    #[error("Unknown glyph {glyph:?} at row {row}, column {col}")]
+    UnknownGlyph {
+        glyph: char,
+        row: usize,
+        col: usize,
+        #[source_code]
+        input: String,
+        #[label("cannot parse this")]
+        span: SourceSpan,
+    },
+
+    #[error("Map is empty")]
+    EmptyMap {
+        #[source_code]
+        input: String,
+        #[label("expected a map here")]
+        span: SourceSpan,
+    },
+
+    #[error("Required marker {marker:?} was not found")]
+    MissingMarker { marker: char },
+
+    #[error("Marker {marker:?} found again at row {row}, column {col}")]
+    DuplicateMarker {
+        marker: char,
+        row: usize,
+        col: usize,
+        #[source_code]
+        input: String,
+        #[label("marker must appear only once")]
+        span: SourceSpan,
+    },
+}
@@ -2,19 +2,50 @@ use glam::IVec2;
 use nom::{
     character::complete::line_ending,
     combinator::opt,
+    error::ErrorKind,
     multi::{many1, separated_list1},
     IResult, Parser,
 };
 use nom_supreme::ParserExt;
 use std::{collections::HashMap, fmt::Debug};
 
-/// Represents a rectangular map of values that is maintained
-/// as a hash-map
+mod cells;
+mod direction;
+mod error;
+mod markers;
+mod regions;
+mod render;
+pub mod search;
+mod sparse;
+mod topology;
+mod transform;
+
+pub use cells::{Digit, Wall};
+pub use direction::{Direction, Direction8};
+pub use error::MapParseError;
+pub use markers::{MarkedMap, Markers};
+pub use regions::{Connectivity, Region, Regions};
+pub use render::{Overlay, ToGlyph};
+pub use sparse::SparseMap;
+pub use topology::{DenseTopology, Topology};
+pub use transform::MapView;
+
+// Used by the `glyph_enum!` macro
+#[doc(hidden)]
+pub use nom;
+
+/// Represents a rectangular map of values.
+///
+/// Values are stored densely in row-major order, so every position
+/// inside the map has a value and iteration order is deterministic.
+///
+/// Maps are [DenseTopology::Bounded] unless changed with [Map::with_topology].
 #[derive(Debug, Clone, PartialEq)]
 pub struct Map<T: Clone + PartialEq + Debug> {
     rows: usize,
     cols: usize,
-    values: HashMap<IVec2, T>,
+    values: Vec<T>,
+    topology: DenseTopology,
 }
 
 impl<T: Clone + PartialEq + Debug> Default for Map<T> {
@@ -23,6 +54,7 @@ impl<T: Clone + PartialEq + Debug> Default for Map<T> {
             rows: 0,
             cols: 0,
             values: Default::default(),
+            topology: DenseTopology::Bounded,
         }
     }
 }
@@ -33,12 +65,56 @@ pub trait Parseable {
 }
 
 impl<T: Clone + PartialEq + Debug> Map<T> {
-    pub fn create(rows: usize, cols: usize, values: HashMap<IVec2, T>) -> Self {
-        Self { rows, cols, values }
+    /// Creates a map out of row-major `values`.
+    ///
+    /// Panics if `values` does not contain exactly `rows * cols` items.
+    pub fn create(rows: usize, cols: usize, values: Vec<T>) -> Self {
+        assert_eq!(
+            rows * cols,
+            values.len(),
+            "A {}x{} map needs exactly {} values",
+            rows,
+            cols,
+            rows * cols
+        );
+        Self {
+            rows,
+            cols,
+            values,
+            topology: DenseTopology::Bounded,
+        }
+    }
+
+    /// Creates a map where every cell has the same `value`.
+    pub fn filled(rows: usize, cols: usize, value: T) -> Self {
+        Self {
+            rows,
+            cols,
+            values: vec![value; rows * cols],
+            topology: DenseTopology::Bounded,
+        }
+    }
+
+    /// Changes how positions outside of the rows and columns are treated.
+    ///
+    /// Use a [SparseMap] for [Topology::Unbounded].
+    pub fn with_topology(mut self, topology: DenseTopology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology.into()
+    }
+
+    /// The position inside the map that `pos` refers to (e.g. after wrapping
+    /// around), or `None` if there is no such position.
+    pub fn normalize(&self, pos: IVec2) -> Option<IVec2> {
+        self.topology().normalize(pos, self.rows, self.cols)
     }
 
     pub fn is_inside(&self, pos: IVec2) -> bool {
-        pos.x >= 0 && (pos.x as usize) < self.cols && pos.y >= 0 && (pos.y as usize) < self.rows
+        self.normalize(pos).is_some()
     }
 
     pub fn row_count(&self) -> usize {
@@ -49,16 +125,51 @@ impl<T: Clone + PartialEq + Debug> Map<T> {
         self.cols
     }
 
+    fn index_of(&self, pos: &IVec2) -> Option<usize> {
+        self.normalize(*pos)
+            .map(|pos| pos.y as usize * self.cols + pos.x as usize)
+    }
+
+    fn position_of(&self, idx: usize) -> IVec2 {
+        IVec2::new((idx % self.cols) as i32, (idx / self.cols) as i32)
+    }
+
     pub fn get(&self, pos: &IVec2) -> Option<&T> {
-        self.values.get(pos)
+        self.index_of(pos).map(|idx| &self.values[idx])
     }
 
     pub fn get_mut(&mut self, pos: &IVec2) -> Option<&mut T> {
-        self.values.get_mut(pos)
+        self.index_of(pos).map(|idx| &mut self.values[idx])
+    }
+
+    /// Value at the normalized `pos`, together with that position.
+    fn normalized_entry(&self, pos: IVec2) -> Option<(IVec2, &T)> {
+        let pos = self.normalize(pos)?;
+        self.get(&pos).map(|v| (pos, v))
+    }
+
+    /// Iterates over the 4 direct neighbours of `pos` that are inside the map.
+    ///
+    /// Neighbours are returned at their normalized positions.
+    pub fn neighbours(&self, pos: IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.normalized_entry(pos + d.vec()))
+    }
+
+    /// Iterates over the 8 neighbours (including diagonals) of `pos` that are inside the map.
+    pub fn neighbours8(&self, pos: IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |d| self.normalized_entry(pos + d.vec()))
     }
 
-    pub fn values_iter(&self) -> impl Iterator<Item = (&IVec2, &T)> {
-        self.values.iter()
+    /// Iterates over all positions and values, row by row.
+    pub fn values_iter(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        self.values
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| (self.position_of(idx), value))
     }
 }
 
@@ -66,40 +177,175 @@ impl<T: PartialEq + Debug + Copy + Clone + Parseable<Item = T>> Map<T> {
     /// Parses the map from a newline-separated list of items
     /// The underlying type generally is parseable from char, but could be multi-char as well (e.g.
     /// integers separated by space)
+    ///
+    /// Fails if rows have different lengths. Use [Map::try_parse] to find out where.
     pub fn parse(input: &str) -> IResult<&str, Self> {
-        separated_list1(
-            line_ending,
-            many1(T::parse)
-                .map(|row_values| row_values.into_iter().enumerate().collect::<Vec<_>>()),
-        )
-        .terminated(opt(line_ending))
-        .map(|all| {
-            all.iter().fold(Map::default(), |mut m, v| {
-                match m.cols {
-                    0 => m.cols = v.len(),
-                    _ => assert_eq!(
-                        m.cols,
-                        v.len(),
-                        "All rows of a map must be of the same length. Got {:?}",
-                        v
-                    ),
+        Self::parse_as::<T>(input)
+    }
+
+    /// Parses the map from newline-separated rows, reporting the row and
+    /// column of any invalid input.
+    ///
+    /// The map ends at the first empty line or at the end of the input.
+    /// Returns the input remaining after the map, starting with that empty line.
+    pub fn try_parse(input: &str) -> Result<(&str, Self), MapParseError> {
+        Self::try_parse_as::<T>(input)
+    }
+}
+
+impl<T: PartialEq + Debug + Copy + Clone> Map<T> {
+    /// Same as [Map::parse], but cells are parsed by `P` instead of `T` itself.
+    ///
+    /// This allows maps of plain values, like `Map::<u8>::parse_as::<Digit>`.
+    pub fn parse_as<P: Parseable<Item = T>>(input: &str) -> IResult<&str, Self> {
+        let (rest, rows) = separated_list1(line_ending, many1(P::parse))
+            .terminated(opt(line_ending))
+            .parse(input)?;
+
+        let cols = rows[0].len();
+        if rows.iter().any(|row| row.len() != cols) {
+            // All rows of a map must be of the same length
+            return Err(nom::Err::Failure(nom::error::Error::new(
+                input,
+                ErrorKind::Verify,
+            )));
+        }
+
+        Ok((
+            rest,
+            Map {
+                rows: rows.len(),
+                cols,
+                values: rows.into_iter().flatten().collect(),
+                topology: DenseTopology::Bounded,
+            },
+        ))
+    }
+
+    /// Same as [Map::try_parse], but cells are parsed by `P` instead of `T` itself.
+    pub fn try_parse_as<P: Parseable<Item = T>>(
+        input: &str,
+    ) -> Result<(&str, Self), MapParseError> {
+        Self::try_parse_marked::<P>(input, None, &mut HashMap::new())
+    }
+
+    /// Implements [Map::try_parse], replacing any `markers` with their background
+    /// cell and recording where they were found.
+    pub(crate) fn try_parse_marked<'a, P: Parseable<Item = T>>(
+        input: &'a str,
+        markers: Option<&Markers<T>>,
+        found: &mut HashMap<char, Vec<IVec2>>,
+    ) -> Result<(&'a str, Self), MapParseError> {
+        let mut map = Map::default();
+        let mut offset = 0;
+
+        while offset < input.len() {
+            let rest = &input[offset..];
+            let line_len = rest.find('\n').unwrap_or(rest.len());
+            let line = &rest[..line_len];
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            if line.is_empty() {
+                break;
+            }
+
+            let (row, starts) =
+                Self::try_parse_row::<P>(input, offset, line, map.rows, markers, found)?;
+
+            match map.cols {
+                0 => map.cols = row.len(),
+                expected if expected != row.len() => {
+                    // the extra cells of a long row, or the end of a short one
+                    let col = expected.min(row.len());
+                    let start = starts.get(col).copied().unwrap_or(offset + line.len());
+                    return Err(MapParseError::RaggedRow {
+                        row: map.rows,
+                        col,
+                        found: row.len(),
+                        expected,
+                        input: input.into(),
+                        span: (start, offset + line.len() - start).into(),
+                    });
                 }
-                for (x, value) in v {
-                    m.values
-                        .insert(IVec2::new(*x as i32, m.rows as i32), *value);
+                _ => {}
+            }
+
+            map.values.extend(row);
+            map.rows += 1;
+            offset = (offset + line_len + 1).min(input.len());
+        }
+
+        if map.rows == 0 {
+            return Err(MapParseError::EmptyMap {
+                input: input.into(),
+                span: (offset, 0).into(),
+            });
+        }
+
+        Ok((&input[offset..], map))
+    }
+
+    /// Parses all the cells of a single row that starts at `offset` in `input`,
+    /// along with the offset in `input` of every cell.
+    fn try_parse_row<P: Parseable<Item = T>>(
+        input: &str,
+        offset: usize,
+        line: &str,
+        row: usize,
+        markers: Option<&Markers<T>>,
+        found: &mut HashMap<char, Vec<IVec2>>,
+    ) -> Result<(Vec<T>, Vec<usize>), MapParseError> {
+        let mut values = Vec::new();
+        let mut starts = Vec::new();
+        let mut rest = line;
+
+        while !rest.is_empty() {
+            starts.push(offset + line.len() - rest.len());
+            if let Some(markers) = markers {
+                let glyph = rest.chars().next().expect("rest is not empty");
+                if markers.contains(glyph) {
+                    let positions = found.entry(glyph).or_default();
+                    if markers.is_required(glyph) && !positions.is_empty() {
+                        return Err(MapParseError::DuplicateMarker {
+                            marker: glyph,
+                            row,
+                            col: values.len(),
+                            input: input.into(),
+                            span: (offset + line.len() - rest.len(), glyph.len_utf8()).into(),
+                        });
+                    }
+                    positions.push(IVec2::new(values.len() as i32, row as i32));
+                    values.push(markers.background());
+                    rest = &rest[glyph.len_utf8()..];
+                    continue;
                 }
-                m.rows += 1;
-                m
-            })
-        })
-        .parse(input)
+            }
+
+            match P::parse(rest) {
+                Ok((next, value)) if next.len() < rest.len() => {
+                    values.push(value);
+                    rest = next;
+                }
+                _ => {
+                    let glyph = rest.chars().next().expect("rest is not empty");
+                    return Err(MapParseError::UnknownGlyph {
+                        glyph,
+                        row,
+                        col: values.len(),
+                        input: input.into(),
+                        span: (offset + line.len() - rest.len(), glyph.len_utf8()).into(),
+                    });
+                }
+            }
+        }
+
+        Ok((values, starts))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use nom::character::complete::{self, satisfy, space0};
-    use nom_supreme::ParserExt;
+    use nom::character::complete::satisfy;
 
     use super::*;
 
@@ -127,27 +373,19 @@ mod tests {
             Map {
                 rows: 2,
                 cols: 3,
-                values: HashMap::from_iter(vec![
-                    (IVec2::new(0, 0), SomeChar::Value('1')),
-                    (IVec2::new(1, 0), SomeChar::Value('2')),
-                    (IVec2::new(2, 0), SomeChar::Value('3')),
-                    (IVec2::new(0, 1), SomeChar::Value('a')),
-                    (IVec2::new(1, 1), SomeChar::Value('b')),
-                    (IVec2::new(2, 1), SomeChar::Value('c')),
-                ]),
+                values: vec![
+                    SomeChar::Value('1'),
+                    SomeChar::Value('2'),
+                    SomeChar::Value('3'),
+                    SomeChar::Value('a'),
+                    SomeChar::Value('b'),
+                    SomeChar::Value('c'),
+                ],
+                topology: DenseTopology::Bounded,
             }
         );
     }
 
-    impl Parseable for u32 {
-        type Item = u32;
-
-        #[tracing::instrument]
-        fn parse(s: &str) -> IResult<&str, Self::Item> {
-            complete::u32.terminated(space0).parse(s)
-        }
-    }
-
     #[test]
     #[tracing_test::traced_test]
     fn parsing_numbers() {
@@ -158,15 +396,163 @@ mod tests {
             Map {
                 rows: 3,
                 cols: 2,
-                values: HashMap::from_iter(vec![
-                    (IVec2::new(0, 0), 1),
-                    (IVec2::new(1, 0), 2),
-                    (IVec2::new(0, 1), 10),
-                    (IVec2::new(1, 1), 20),
-                    (IVec2::new(0, 2), 123),
-                    (IVec2::new(1, 2), 321),
-                ]),
+                values: vec![1, 2, 10, 20, 123, 321],
+                topology: DenseTopology::Bounded,
             }
         );
     }
+
+    #[test]
+    fn parse_ragged_rows_fails() {
+        assert!(Map::<SomeChar>::parse("123\nab\n").is_err());
+    }
+
+    #[test]
+    fn try_parse_valid() {
+        let (rest, m) = Map::<SomeChar>::try_parse("12\r\nab\n").expect("valid input");
+
+        assert_eq!(rest, "");
+        assert_eq!(m, Map::<SomeChar>::parse("12\nab").expect("valid input").1);
+
+        let (rest, m) = Map::<u32>::try_parse("1 2\n10 20\n\nrest").expect("valid input");
+        assert_eq!(rest, "\nrest");
+        assert_eq!(m, Map::create(2, 2, vec![1, 2, 10, 20]));
+    }
+
+    #[test]
+    fn try_parse_errors() {
+        let input = "123\nab\n";
+        assert_eq!(
+            Map::<SomeChar>::try_parse(input),
+            Err(MapParseError::RaggedRow {
+                row: 1,
+                col: 2,
+                found: 2,
+                expected: 3,
+                input: input.into(),
+                span: (6, 0).into(),
+            })
+        );
+
+        let input = "1 2\n10 20 30\n";
+        assert_eq!(
+            Map::<u32>::try_parse(input),
+            Err(MapParseError::RaggedRow {
+                row: 1,
+                col: 2,
+                found: 3,
+                expected: 2,
+                input: input.into(),
+                span: (10, 2).into(),
+            })
+        );
+
+        let input = "1 2\n3 x\n";
+        assert_eq!(
+            Map::<u32>::try_parse(input),
+            Err(MapParseError::UnknownGlyph {
+                glyph: 'x',
+                row: 1,
+                col: 1,
+                input: input.into(),
+                span: (6, 1).into(),
+            })
+        );
+
+        assert_eq!(
+            Map::<u32>::try_parse(""),
+            Err(MapParseError::EmptyMap {
+                input: "".into(),
+                span: (0, 0).into(),
+            })
+        );
+    }
+
+    #[test]
+    fn dense_access() {
+        let mut m = Map::<u32>::parse("1 2 3\n4 5 6").expect("valid input").1;
+
+        assert_eq!(m.get(&IVec2::new(0, 0)), Some(&1));
+        assert_eq!(m.get(&IVec2::new(2, 0)), Some(&3));
+        assert_eq!(m.get(&IVec2::new(1, 1)), Some(&5));
+        assert_eq!(m.get(&IVec2::new(3, 0)), None);
+        assert_eq!(m.get(&IVec2::new(0, 2)), None);
+        assert_eq!(m.get(&IVec2::new(-1, 0)), None);
+
+        *m.get_mut(&IVec2::new(2, 1)).expect("valid position") = 60;
+        assert_eq!(m.get(&IVec2::new(2, 1)), Some(&60));
+        assert!(m.get_mut(&IVec2::new(0, -1)).is_none());
+    }
+
+    #[test]
+    fn neighbours_clip_at_bounds() {
+        let m = Map::<u32>::parse("1 2 3\n4 5 6\n7 8 9")
+            .expect("valid input")
+            .1;
+
+        assert_eq!(
+            m.neighbours(IVec2::new(1, 1))
+                .map(|(_, v)| *v)
+                .collect::<Vec<_>>(),
+            vec![2, 6, 8, 4]
+        );
+        assert_eq!(
+            m.neighbours(IVec2::new(0, 0)).collect::<Vec<_>>(),
+            vec![(IVec2::new(1, 0), &2), (IVec2::new(0, 1), &4)]
+        );
+        assert_eq!(
+            m.neighbours8(IVec2::new(1, 1))
+                .map(|(_, v)| *v)
+                .collect::<Vec<_>>(),
+            vec![2, 3, 6, 9, 8, 7, 4, 1]
+        );
+        assert_eq!(
+            m.neighbours8(IVec2::new(2, 2))
+                .map(|(_, v)| *v)
+                .collect::<Vec<_>>(),
+            vec![6, 8, 5]
+        );
+    }
+
+    #[test]
+    fn wrapping_topology() {
+        let mut m = Map::<u32>::parse("1 2 3\n4 5 6")
+            .expect("valid input")
+            .1
+            .with_topology(DenseTopology::Wrapping);
+
+        assert!(m.is_inside(IVec2::new(-1, 5)));
+        assert_eq!(m.normalize(IVec2::new(-1, 5)), Some(IVec2::new(2, 1)));
+        assert_eq!(m.get(&IVec2::new(3, -2)), Some(&1));
+
+        *m.get_mut(&IVec2::new(-3, 3)).expect("wraps around") = 40;
+        assert_eq!(m.get(&IVec2::new(0, 1)), Some(&40));
+
+        assert_eq!(
+            m.neighbours(IVec2::new(0, 0)).collect::<Vec<_>>(),
+            vec![
+                (IVec2::new(0, 1), &40),
+                (IVec2::new(1, 0), &2),
+                (IVec2::new(0, 1), &40),
+                (IVec2::new(2, 0), &3)
+            ]
+        );
+        assert_eq!(m.neighbours8(IVec2::new(0, 0)).count(), 8);
+        assert_eq!(m.topology(), Topology::Wrapping);
+    }
+
+    #[test]
+    fn values_iter_is_row_major() {
+        let m = Map::<SomeChar>::parse("ab\ncd").expect("valid input").1;
+
+        assert_eq!(
+            m.values_iter().collect::<Vec<_>>(),
+            vec![
+                (IVec2::new(0, 0), &SomeChar::Value('a')),
+                (IVec2::new(1, 0), &SomeChar::Value('b')),
+                (IVec2::new(0, 1), &SomeChar::Value('c')),
+                (IVec2::new(1, 1), &SomeChar::Value('d')),
+            ]
+        );
+    }
 }
@@ -0,0 +1,179 @@
+use glam::IVec2;
+use std::{collections::HashMap, fmt::Debug};
+
+use crate::{Map, MapParseError, Parseable};
+
+/// Describes special glyphs (like a start `S` or robot `@`) to pull out of a
+/// map while parsing it.
+///
+/// Every marker is replaced in the map by a background cell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Markers<T> {
+    background: T,
+    required: Vec<char>,
+    any: Vec<char>,
+}
+
+impl<T: Copy> Markers<T> {
+    /// Markers will be replaced by `background` in the parsed map.
+    pub fn new(background: T) -> Self {
+        Self {
+            background,
+            required: Vec::new(),
+            any: Vec::new(),
+        }
+    }
+
+    /// `marker` must appear exactly once in the map.
+    pub fn required(mut self, marker: char) -> Self {
+        self.required.push(marker);
+        self
+    }
+
+    /// `marker` may appear any number of times in the map.
+    pub fn any(mut self, marker: char) -> Self {
+        self.any.push(marker);
+        self
+    }
+
+    pub(crate) fn background(&self) -> T {
+        self.background
+    }
+
+    pub(crate) fn contains(&self, marker: char) -> bool {
+        self.required.contains(&marker) || self.any.contains(&marker)
+    }
+
+    pub(crate) fn is_required(&self, marker: char) -> bool {
+        self.required.contains(&marker)
+    }
+}
+
+/// A map together with the positions of all markers found in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkedMap<T: Clone + PartialEq + Debug> {
+    pub map: Map<T>,
+    pub markers: HashMap<char, Vec<IVec2>>,
+}
+
+impl<T: Clone + PartialEq + Debug> MarkedMap<T> {
+    /// Position of the first occurrence of `marker`, if any.
+    ///
+    /// Required markers always have exactly one position.
+    pub fn marker(&self, marker: char) -> Option<IVec2> {
+        self.markers
+            .get(&marker)
+            .and_then(|positions| positions.first())
+            .copied()
+    }
+
+    /// All positions where `marker` was found, in row-major order.
+    pub fn marker_positions(&self, marker: char) -> &[IVec2] {
+        self.markers
+            .get(&marker)
+            .map(|positions| positions.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+impl<T: PartialEq + Debug + Copy + Clone + Parseable<Item = T>> Map<T> {
+    /// Same as [Map::try_parse], however `markers` are extracted from the map
+    /// and replaced by their background cell.
+    pub fn try_parse_with_markers<'a>(
+        input: &'a str,
+        markers: &Markers<T>,
+    ) -> Result<(&'a str, MarkedMap<T>), MapParseError> {
+        let mut found = HashMap::new();
+        let (rest, map) = Self::try_parse_marked::<T>(input, Some(markers), &mut found)?;
+
+        if let Some(marker) = markers
+            .required
+            .iter()
+            .find(|marker| !found.contains_key(marker))
+        {
+            return Err(MapParseError::MissingMarker { marker: *marker });
+        }
+
+        Ok((
+            rest,
+            MarkedMap {
+                map,
+                markers: found,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{branch::alt, bytes::complete::tag, IResult, Parser};
+    use nom_supreme::ParserExt;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    enum Cell {
+        Wall,
+        Empty,
+    }
+
+    impl Parseable for Cell {
+        type Item = Cell;
+
+        fn parse(s: &str) -> IResult<&str, Self::Item> {
+            alt((tag("#").value(Cell::Wall), tag(".").value(Cell::Empty))).parse(s)
+        }
+    }
+
+    #[test]
+    fn extract_markers() {
+        let (rest, m) = Map::<Cell>::try_parse_with_markers(
+            "#S.\n.O#\nO.E\n",
+            &Markers::new(Cell::Empty)
+                .required('S')
+                .required('E')
+                .any('O')
+                .any('@'),
+        )
+        .expect("valid input");
+
+        assert_eq!(rest, "");
+        assert_eq!(m.map, Map::<Cell>::parse("#..\n..#\n...").expect("valid").1);
+        assert_eq!(m.marker('S'), Some(IVec2::new(1, 0)));
+        assert_eq!(m.marker('E'), Some(IVec2::new(2, 2)));
+        assert_eq!(
+            m.marker_positions('O'),
+            &[IVec2::new(1, 1), IVec2::new(0, 2)]
+        );
+        assert_eq!(m.marker('@'), None);
+        assert!(m.marker_positions('@').is_empty());
+    }
+
+    #[test]
+    fn marker_errors() {
+        let markers = Markers::new(Cell::Empty).required('S').required('E');
+
+        assert_eq!(
+            Map::<Cell>::try_parse_with_markers("#S.\n..#\n", &markers),
+            Err(MapParseError::MissingMarker { marker: 'E' })
+        );
+
+        let input = "#S.\n.S#\n..E";
+        assert_eq!(
+            Map::<Cell>::try_parse_with_markers(input, &markers),
+            Err(MapParseError::DuplicateMarker {
+                marker: 'S',
+                row: 1,
+                col: 1,
+                input: input.into(),
+                span: (5, 1).into(),
+            })
+        );
+
+        // markers are only understood when requested
+        assert!(matches!(
+            Map::<Cell>::try_parse("#S.\n..E"),
+            Err(MapParseError::UnknownGlyph { glyph: 'S', .. })
+        ));
+    }
+}
@@ -0,0 +1,266 @@
+use glam::IVec2;
+use std::fmt::Debug;
+
+use crate::{Direction, Direction8, Map};
+
+/// Which cells count as touching when flood-filling regions.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Connectivity {
+    /// Only cells sharing an edge (N/E/S/W)
+    Four,
+    /// Cells sharing an edge or a corner
+    Eight,
+}
+
+const FOUR: [IVec2; 4] = [
+    Direction::ALL[0].vec(),
+    Direction::ALL[1].vec(),
+    Direction::ALL[2].vec(),
+    Direction::ALL[3].vec(),
+];
+
+const EIGHT: [IVec2; 8] = [
+    Direction8::ALL[0].vec(),
+    Direction8::ALL[1].vec(),
+    Direction8::ALL[2].vec(),
+    Direction8::ALL[3].vec(),
+    Direction8::ALL[4].vec(),
+    Direction8::ALL[5].vec(),
+    Direction8::ALL[6].vec(),
+    Direction8::ALL[7].vec(),
+];
+
+impl Connectivity {
+    fn offsets(&self) -> &'static [IVec2] {
+        match self {
+            Connectivity::Four => &FOUR,
+            Connectivity::Eight => &EIGHT,
+        }
+    }
+}
+
+/// A connected group of equivalent cells.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region {
+    pub label: usize,
+    /// Positions of all the cells, in flood-fill order
+    pub cells: Vec<IVec2>,
+    /// Number of cell edges that border another region or the map edge
+    pub perimeter: usize,
+    /// Number of straight sides, where adjacent collinear edges count once
+    pub sides: usize,
+    /// Top-left corner of the bounding box (inclusive)
+    pub min: IVec2,
+    /// Bottom-right corner of the bounding box (inclusive)
+    pub max: IVec2,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+/// All the regions of a map, see [Map::regions].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regions {
+    labels: Map<usize>,
+    regions: Vec<Region>,
+}
+
+impl Regions {
+    /// Label of the region that contains `pos`
+    pub fn label_at(&self, pos: &IVec2) -> Option<usize> {
+        self.labels.get(pos).copied()
+    }
+
+    pub fn region_at(&self, pos: &IVec2) -> Option<&Region> {
+        self.label_at(pos).map(|label| &self.regions[label])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Region> {
+        self.regions.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+}
+
+/// Direction in which to look for a cell sharing the same wall
+fn wall_neighbour(d: Direction) -> Direction {
+    match d {
+        Direction::N | Direction::S => Direction::E,
+        Direction::E | Direction::W => Direction::S,
+    }
+}
+
+impl<T: Clone + PartialEq + Debug> Map<T> {
+    /// Splits the map into connected regions of cells for which `same` holds
+    /// between neighbours.
+    ///
+    /// Labels are assigned in row-major order of the first cell of each region.
+    pub fn regions(&self, connectivity: Connectivity, same: impl Fn(&T, &T) -> bool) -> Regions {
+        let offsets = connectivity.offsets();
+        let mut labels: Map<Option<usize>> = Map::filled(self.row_count(), self.col_count(), None);
+        let mut all_cells = Vec::new();
+
+        for (start, _) in self.values_iter() {
+            if labels.get(&start) != Some(&None) {
+                continue;
+            }
+
+            let label = all_cells.len();
+            let mut cells = Vec::new();
+            let mut to_check = vec![start];
+            *labels.get_mut(&start).expect("valid position") = Some(label);
+
+            while let Some(pos) = to_check.pop() {
+                cells.push(pos);
+                let value = self.get(&pos).expect("valid position");
+
+                for offset in offsets.iter() {
+                    let next = pos + *offset;
+                    match (self.get(&next), labels.get_mut(&next)) {
+                        (Some(other), Some(next_label @ None)) if same(value, other) => {
+                            *next_label = Some(label);
+                            to_check.push(next);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            all_cells.push(cells);
+        }
+
+        let labels = Map::create(
+            self.row_count(),
+            self.col_count(),
+            labels
+                .values_iter()
+                .map(|(_, label)| label.expect("all cells are labeled"))
+                .collect(),
+        );
+
+        let regions = all_cells
+            .into_iter()
+            .enumerate()
+            .map(|(label, cells)| {
+                let inside = |pos: IVec2| labels.get(&pos) == Some(&label);
+
+                let mut perimeter = 0;
+                let mut sides = 0;
+                for pos in cells.iter() {
+                    for d in Direction::ALL {
+                        if inside(*pos + d.vec()) {
+                            continue;
+                        }
+                        perimeter += 1;
+
+                        // Only count a side once: skip this edge if the neighbouring
+                        // cell along the wall continues the same wall.
+                        let n1 = *pos + wall_neighbour(d).vec();
+                        if !inside(n1) || inside(n1 + d.vec()) {
+                            sides += 1;
+                        }
+                    }
+                }
+
+                Region {
+                    label,
+                    min: cells.iter().copied().reduce(IVec2::min).expect("has cells"),
+                    max: cells.iter().copied().reduce(IVec2::max).expect("has cells"),
+                    cells,
+                    perimeter,
+                    sides,
+                }
+            })
+            .collect();
+
+        Regions { labels, regions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn garden(s: &str) -> Map<char> {
+        let rows = s.lines().count();
+        let cols = s.lines().next().expect("not empty").len();
+        Map::create(rows, cols, s.lines().flat_map(|l| l.chars()).collect())
+    }
+
+    #[test]
+    fn four_connected() {
+        let m = garden("AAAA\nBBCD\nBBCC\nEEEC");
+        let regions = m.regions(Connectivity::Four, |a, b| a == b);
+
+        assert_eq!(regions.len(), 5);
+        assert_eq!(
+            regions
+                .iter()
+                .map(|r| (
+                    m.get(&r.cells[0]).copied().expect("valid"),
+                    r.area(),
+                    r.perimeter,
+                    r.sides
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4),
+            ]
+        );
+
+        let c = regions
+            .region_at(&IVec2::new(2, 1))
+            .expect("valid position");
+        assert_eq!(c.label, 2);
+        assert_eq!(c.min, IVec2::new(2, 1));
+        assert_eq!(c.max, IVec2::new(3, 3));
+        assert_eq!(regions.label_at(&IVec2::new(3, 3)), Some(2));
+        assert_eq!(regions.label_at(&IVec2::new(4, 3)), None);
+    }
+
+    #[test]
+    fn sides_with_holes() {
+        let m = garden("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA");
+        let regions = m.regions(Connectivity::Four, |a, b| a == b);
+
+        assert_eq!(regions.len(), 3);
+        assert_eq!(
+            regions.region_at(&IVec2::new(0, 0)).map(|r| r.sides),
+            Some(12)
+        );
+        assert_eq!(
+            regions.region_at(&IVec2::new(3, 1)).map(|r| r.sides),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn eight_connected() {
+        let m = garden("#..\n.#.\n..#");
+
+        assert_eq!(m.regions(Connectivity::Four, |a, b| a == b).len(), 5);
+
+        let regions = m.regions(Connectivity::Eight, |a, b| a == b);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(
+            regions.region_at(&IVec2::new(2, 2)).map(|r| r.area()),
+            Some(3)
+        );
+        assert_eq!(
+            regions.region_at(&IVec2::new(1, 0)).map(|r| r.area()),
+            Some(6)
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use glam::IVec2;
+use std::{
+    collections::HashSet,
+    fmt::{Debug, Display},
+};
+
+use crate::{Map, SparseMap};
+
+/// Converts a map cell back into the character it was parsed from.
+///
+/// This is the inverse of [crate::Parseable] for single-character cells,
+/// so that `Map::parse(&map.render())` gives back the same map.
+pub trait ToGlyph {
+    fn glyph(&self) -> char;
+}
+
+impl ToGlyph for char {
+    fn glyph(&self) -> char {
+        *self
+    }
+}
+
+/// Something drawn on top of a map when rendering, like a path or robots.
+pub trait Overlay {
+    /// The character to draw at `pos`, or `None` to show the underlying cell.
+    fn glyph_at(&self, pos: IVec2) -> Option<char>;
+}
+
+/// A single marked position.
+impl Overlay for (char, IVec2) {
+    fn glyph_at(&self, pos: IVec2) -> Option<char> {
+        (self.1 == pos).then_some(self.0)
+    }
+}
+
+/// A set of marked positions, like visited cells.
+impl Overlay for (char, &HashSet<IVec2>) {
+    fn glyph_at(&self, pos: IVec2) -> Option<char> {
+        self.1.contains(&pos).then_some(self.0)
+    }
+}
+
+/// A list of marked positions, like a path.
+impl Overlay for (char, &[IVec2]) {
+    fn glyph_at(&self, pos: IVec2) -> Option<char> {
+        self.1.contains(&pos).then_some(self.0)
+    }
+}
+
+impl<T: Clone + PartialEq + Debug> Map<T> {
+    /// Renders the map one row per line, using `glyph` to draw every cell.
+    pub fn render_with(&self, glyph: impl Fn(IVec2, &T) -> char) -> String {
+        let mut s = String::with_capacity((self.col_count() + 1) * self.row_count());
+        for (pos, value) in self.values_iter() {
+            s.push(glyph(pos, value));
+            if pos.x as usize + 1 == self.col_count() {
+                s.push('\n');
+            }
+        }
+        s
+    }
+}
+
+impl<T: Clone + PartialEq + Debug + ToGlyph> Map<T> {
+    /// Renders the map in the same format that [Map::parse] accepts.
+    pub fn render(&self) -> String {
+        self.render_with(|_, value| value.glyph())
+    }
+
+    /// Renders the map with `overlays` drawn on top of it.
+    ///
+    /// Earlier overlays take precedence over later ones.
+    pub fn render_overlays(&self, overlays: &[&dyn Overlay]) -> String {
+        self.render_with(|pos, value| {
+            overlays
+                .iter()
+                .find_map(|o| o.glyph_at(pos))
+                .unwrap_or_else(|| value.glyph())
+        })
+    }
+}
+
+impl<T: Clone + PartialEq + Debug> SparseMap<T> {
+    /// Renders the area within [SparseMap::bounds] one row per line, using
+    /// `glyph` to draw every position whether it has a value or not.
+    pub fn render_with(&self, glyph: impl Fn(IVec2, Option<&T>) -> char) -> String {
+        let Some((min, max)) = self.bounds() else {
+            return String::new();
+        };
+
+        let mut s = String::with_capacity(((max.x - min.x + 2) * (max.y - min.y + 1)) as usize);
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let pos = IVec2::new(x, y);
+                s.push(glyph(pos, self.get(&pos)));
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl<T: Clone + PartialEq + Debug + ToGlyph> SparseMap<T> {
+    /// Renders the map, drawing `empty` wherever there is no value.
+    pub fn render(&self, empty: char) -> String {
+        self.render_with(|_, value| value.map(|v| v.glyph()).unwrap_or(empty))
+    }
+}
+
+impl<T: Clone + PartialEq + Debug + ToGlyph> Display for Map<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{branch::alt, bytes::complete::tag, IResult, Parser};
+    use nom_supreme::ParserExt;
+
+    use super::*;
+    use crate::Parseable;
+
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    enum Cell {
+        Wall,
+        Empty,
+    }
+
+    impl Parseable for Cell {
+        type Item = Cell;
+
+        fn parse(s: &str) -> IResult<&str, Self::Item> {
+            alt((tag("#").value(Cell::Wall), tag(".").value(Cell::Empty))).parse(s)
+        }
+    }
+
+    impl ToGlyph for Cell {
+        fn glyph(&self) -> char {
+            match self {
+                Cell::Wall => '#',
+                Cell::Empty => '.',
+            }
+        }
+    }
+
+    #[test]
+    fn render_round_trip() {
+        let input = "#..#\n.#..\n...#\n";
+        let m = Map::<Cell>::parse(input).expect("valid input").1;
+
+        assert_eq!(m.render(), input);
+        assert_eq!(format!("{}", m), input);
+        assert_eq!(Map::<Cell>::parse(&m.render()).expect("valid input").1, m);
+    }
+
+    #[test]
+    fn render_overlays() {
+        let m = Map::<Cell>::parse("#..\n...\n..#").expect("valid input").1;
+
+        let visited = HashSet::from([IVec2::new(1, 0), IVec2::new(1, 1)]);
+        let path = [IVec2::new(1, 1), IVec2::new(1, 2)];
+
+        assert_eq!(
+            m.render_overlays(&[
+                &('@', IVec2::new(2, 0)),
+                &('O', path.as_slice()),
+                &('X', &visited)
+            ]),
+            "#X@\n.O.\n.O#\n"
+        );
+    }
+
+    #[test]
+    fn render_sparse() {
+        let mut m = SparseMap::create(2, 3, Default::default());
+        m.insert(IVec2::new(1, 1), '#');
+        assert_eq!(m.render('.'), "...\n.#.\n");
+
+        let mut m = SparseMap::unbounded();
+        m.insert(IVec2::new(-1, -1), '#');
+        m.insert(IVec2::new(1, 0), 'O');
+        assert_eq!(m.render('.'), "#..\n..O\n");
+        assert_eq!(SparseMap::<char>::unbounded().render('.'), "");
+    }
+}
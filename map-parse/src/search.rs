@@ -0,0 +1,273 @@
+//! Path finding over maps.
+//!
+//! [Map::bfs_distances] covers plain "how many steps" questions. For searches
+//! where the state is more than a position (e.g. position and heading) or
+//! moves have different costs, use [dijkstra] with a custom successor function.
+
+use glam::IVec2;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt::Debug,
+    hash::Hash,
+};
+
+use crate::Map;
+
+impl<T: Clone + PartialEq + Debug> Map<T> {
+    /// Number of steps needed to reach every cell from `start`, moving
+    /// in the 4 directions and only over cells for which `passable` is true.
+    ///
+    /// Unreachable cells have a `None` distance.
+    pub fn bfs_distances(
+        &self,
+        start: IVec2,
+        passable: impl Fn(IVec2, &T) -> bool,
+    ) -> Map<Option<usize>> {
+        let mut distances = Map::filled(self.row_count(), self.col_count(), None);
+
+        match self.get(&start) {
+            Some(value) if passable(start, value) => {}
+            _ => return distances,
+        }
+
+        let mut to_check = VecDeque::new();
+        *distances.get_mut(&start).expect("start is inside") = Some(0);
+        to_check.push_back((start, 0));
+
+        while let Some((pos, distance)) = to_check.pop_front() {
+            for (next, value) in self.neighbours(pos) {
+                let next_distance = distances.get_mut(&next).expect("neighbours are inside");
+                if next_distance.is_none() && passable(next, value) {
+                    *next_distance = Some(distance + 1);
+                    to_check.push_back((next, distance + 1));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Smallest number of steps to get from `start` to `goal`, moving only over
+    /// cells for which `passable` is true.
+    pub fn shortest_path_len(
+        &self,
+        start: IVec2,
+        goal: IVec2,
+        passable: impl Fn(IVec2, &T) -> bool,
+    ) -> Option<usize> {
+        self.bfs_distances(start, passable)
+            .get(&goal)
+            .copied()
+            .flatten()
+    }
+}
+
+/// Result of a [dijkstra] search: the cheapest cost to reach every
+/// reachable state, plus enough information to walk back all the
+/// cheapest paths.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dijkstra<S: Eq + Hash> {
+    costs: HashMap<S, usize>,
+    predecessors: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash> Dijkstra<S> {
+    pub fn cost(&self, state: &S) -> Option<usize> {
+        self.costs.get(state).copied()
+    }
+
+    pub fn costs(&self) -> &HashMap<S, usize> {
+        &self.costs
+    }
+
+    /// Cheapest cost to reach any of the `ends` states.
+    pub fn min_cost<'a>(&self, ends: impl IntoIterator<Item = &'a S>) -> Option<usize>
+    where
+        S: 'a,
+    {
+        ends.into_iter().filter_map(|s| self.cost(s)).min()
+    }
+
+    /// All states that are on some cheapest path from a start to the
+    /// cheapest of `ends` (including the starts and ends themselves).
+    pub fn shortest_path_states(&self, ends: &[S]) -> HashSet<S> {
+        let mut seen = HashSet::new();
+
+        let Some(best) = self.min_cost(ends) else {
+            return seen;
+        };
+
+        let mut to_check = ends
+            .iter()
+            .filter(|s| self.cost(s) == Some(best))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        while let Some(state) = to_check.pop() {
+            if !seen.insert(state.clone()) {
+                continue;
+            }
+            if let Some(previous) = self.predecessors.get(&state) {
+                to_check.extend(previous.iter().cloned());
+            }
+        }
+
+        seen
+    }
+
+    /// One of the cheapest paths from a start to `end`, starting state first.
+    pub fn path_to(&self, end: &S) -> Option<Vec<S>> {
+        self.cost(end)?;
+
+        let mut path = vec![end.clone()];
+        while let Some(previous) = self
+            .predecessors
+            .get(path.last().expect("path is not empty"))
+            .and_then(|p| p.first())
+        {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Computes the cheapest cost from any of the `starts` to every reachable state.
+///
+/// `successors` returns the states reachable from a given state together
+/// with the (positive) cost of moving there.
+pub fn dijkstra<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+) -> Dijkstra<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut costs = HashMap::new();
+    let mut predecessors: HashMap<S, Vec<S>> = HashMap::new();
+
+    // The heap refers to states by index, so that states do not need to be `Ord`
+    let mut states = Vec::new();
+    let mut to_check = BinaryHeap::new();
+
+    for start in starts {
+        costs.insert(start.clone(), 0);
+        to_check.push(Reverse((0, states.len())));
+        states.push(start);
+    }
+
+    while let Some(Reverse((cost, idx))) = to_check.pop() {
+        let state = states[idx].clone();
+        if costs.get(&state).is_some_and(|c| *c < cost) {
+            continue; // already reached cheaper
+        }
+
+        for (next, step_cost) in successors(&state) {
+            let next_cost = cost + step_cost;
+            match costs.get(&next) {
+                Some(c) if *c < next_cost => continue,
+                Some(c) if *c == next_cost => {
+                    predecessors.entry(next).or_default().push(state.clone());
+                    continue;
+                }
+                _ => {}
+            }
+
+            costs.insert(next.clone(), next_cost);
+            predecessors.insert(next.clone(), vec![state.clone()]);
+            to_check.push(Reverse((next_cost, states.len())));
+            states.push(next);
+        }
+    }
+
+    Dijkstra {
+        costs,
+        predecessors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Direction;
+
+    fn maze() -> Map<char> {
+        Map::create(
+            3,
+            4,
+            "....\
+             .##.\
+             ...#"
+                .chars()
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn bfs() {
+        let m = maze();
+        let d = m.bfs_distances(IVec2::new(0, 0), |_, c| *c != '#');
+
+        assert_eq!(d.get(&IVec2::new(0, 0)), Some(&Some(0)));
+        assert_eq!(d.get(&IVec2::new(3, 1)), Some(&Some(4)));
+        assert_eq!(d.get(&IVec2::new(2, 2)), Some(&Some(4)));
+        assert_eq!(d.get(&IVec2::new(1, 1)), Some(&None));
+        assert_eq!(d.get(&IVec2::new(3, 2)), Some(&None));
+
+        assert_eq!(
+            m.shortest_path_len(IVec2::new(0, 2), IVec2::new(3, 1), |_, c| *c != '#'),
+            Some(6)
+        );
+        assert_eq!(
+            m.shortest_path_len(IVec2::new(0, 0), IVec2::new(3, 2), |_, c| *c != '#'),
+            None
+        );
+    }
+
+    #[test]
+    fn dijkstra_with_turns() {
+        let m = maze();
+
+        // Moving costs 1, turning costs 10
+        let result = dijkstra([(IVec2::new(0, 0), Direction::E)], |(pos, d)| {
+            let mut next = vec![((*pos, d.turn_left()), 10), ((*pos, d.turn_right()), 10)];
+            if m.get(&(*pos + d.vec())).is_some_and(|c| *c != '#') {
+                next.push(((*pos + d.vec(), *d), 1));
+            }
+            next
+        });
+
+        let ends = Direction::ALL.map(|d| (IVec2::new(3, 1), d));
+        assert_eq!(result.min_cost(&ends), Some(14));
+        assert_eq!(
+            result.path_to(&(IVec2::new(3, 1), Direction::S)),
+            Some(vec![
+                (IVec2::new(0, 0), Direction::E),
+                (IVec2::new(1, 0), Direction::E),
+                (IVec2::new(2, 0), Direction::E),
+                (IVec2::new(3, 0), Direction::E),
+                (IVec2::new(3, 0), Direction::S),
+                (IVec2::new(3, 1), Direction::S),
+            ])
+        );
+    }
+
+    #[test]
+    fn all_shortest_paths() {
+        let m = Map::filled(3, 3, '.');
+
+        // every monotonic path from a corner to the opposite one is shortest
+        let result = dijkstra([IVec2::new(0, 0)], |pos| {
+            m.neighbours(*pos).map(|(p, _)| (p, 1)).collect::<Vec<_>>()
+        });
+
+        assert_eq!(result.shortest_path_states(&[IVec2::new(2, 2)]).len(), 9);
+        assert_eq!(
+            result.shortest_path_states(&[IVec2::new(1, 0)]),
+            HashSet::from([IVec2::new(0, 0), IVec2::new(1, 0)])
+        );
+        assert_eq!(result.path_to(&IVec2::new(2, 2)).map(|p| p.len()), Some(5));
+    }
+}
@@ -0,0 +1,258 @@
+use glam::IVec2;
+use std::{collections::HashMap, fmt::Debug};
+
+use crate::{Direction, Direction8, Map, Topology};
+
+/// Represents a map where only some positions have values,
+/// maintained as a hash-map.
+///
+/// Prefer [Map] for maps where (almost) every cell is set: lookups there
+/// do not need hashing.
+///
+/// Sparse maps are rectangular unless [Topology::Unbounded], in which case
+/// values can be anywhere and the rows and columns are unused.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMap<T: Clone + PartialEq + Debug> {
+    rows: usize,
+    cols: usize,
+    values: HashMap<IVec2, T>,
+    topology: Topology,
+}
+
+impl<T: Clone + PartialEq + Debug> Default for SparseMap<T> {
+    fn default() -> Self {
+        Self {
+            rows: 0,
+            cols: 0,
+            values: Default::default(),
+            topology: Topology::Bounded,
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + Debug> SparseMap<T> {
+    pub fn create(rows: usize, cols: usize, values: HashMap<IVec2, T>) -> Self {
+        Self {
+            rows,
+            cols,
+            values,
+            topology: Topology::Bounded,
+        }
+    }
+
+    /// An empty map where values can be set at any position.
+    pub fn unbounded() -> Self {
+        Self::default().with_topology(Topology::Unbounded)
+    }
+
+    /// Changes how positions outside of the rows and columns are treated.
+    ///
+    /// Values already set move to their position in the new topology.
+    /// Panics if one has no such position, or two end up at the same one.
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+
+        let values = std::mem::take(&mut self.values);
+        for (pos, value) in values {
+            let Some(normalized) = self.normalize(pos) else {
+                panic!("{} is outside the map", pos);
+            };
+            if self.values.insert(normalized, value).is_some() {
+                panic!("{} is set more than once", normalized);
+            }
+        }
+        self
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// The position inside the map that `pos` refers to (e.g. after wrapping
+    /// around), or `None` if there is no such position.
+    pub fn normalize(&self, pos: IVec2) -> Option<IVec2> {
+        self.topology.normalize(pos, self.rows, self.cols)
+    }
+
+    pub fn is_inside(&self, pos: IVec2) -> bool {
+        self.normalize(pos).is_some()
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.rows
+    }
+
+    pub fn col_count(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, pos: &IVec2) -> Option<&T> {
+        self.values.get(&self.normalize(*pos)?)
+    }
+
+    pub fn get_mut(&mut self, pos: &IVec2) -> Option<&mut T> {
+        let pos = self.normalize(*pos)?;
+        self.values.get_mut(&pos)
+    }
+
+    /// Sets the value at `pos`, returning the previous value if any.
+    ///
+    /// Panics if `pos` is outside the map.
+    pub fn insert(&mut self, pos: IVec2, value: T) -> Option<T> {
+        let Some(pos) = self.normalize(pos) else {
+            panic!("{} is outside the map", pos);
+        };
+        self.values.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: &IVec2) -> Option<T> {
+        let pos = self.normalize(*pos)?;
+        self.values.remove(&pos)
+    }
+
+    /// Iterates over the 4 direct neighbours of `pos` that have a value.
+    ///
+    /// Neighbours are returned at their normalized positions.
+    pub fn neighbours(&self, pos: IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        Direction::ALL.into_iter().filter_map(move |d| {
+            let pos = self.normalize(pos + d.vec())?;
+            self.values.get(&pos).map(|v| (pos, v))
+        })
+    }
+
+    /// Iterates over the 8 neighbours (including diagonals) of `pos` that
+    /// have a value.
+    pub fn neighbours8(&self, pos: IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        Direction8::ALL.into_iter().filter_map(move |d| {
+            let pos = self.normalize(pos + d.vec())?;
+            self.values.get(&pos).map(|v| (pos, v))
+        })
+    }
+
+    /// Top-left and bottom-right corners (inclusive) of the area to consider,
+    /// e.g. when rendering.
+    ///
+    /// For unbounded maps this is the smallest rectangle containing all
+    /// values, or `None` if there are none.
+    pub fn bounds(&self) -> Option<(IVec2, IVec2)> {
+        match self.topology {
+            Topology::Unbounded => Some((
+                self.values.keys().copied().reduce(IVec2::min)?,
+                self.values.keys().copied().reduce(IVec2::max)?,
+            )),
+            _ if self.rows == 0 || self.cols == 0 => None,
+            _ => Some((
+                IVec2::ZERO,
+                IVec2::new(self.cols as i32 - 1, self.rows as i32 - 1),
+            )),
+        }
+    }
+
+    /// Iterates over all set positions and values, in no particular order.
+    pub fn values_iter(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        self.values.iter().map(|(pos, value)| (*pos, value))
+    }
+}
+
+impl<T: Clone + PartialEq + Debug> From<Map<T>> for SparseMap<T> {
+    fn from(value: Map<T>) -> Self {
+        Self {
+            rows: value.row_count(),
+            cols: value.col_count(),
+            values: value
+                .values_iter()
+                .map(|(pos, v)| (pos, v.clone()))
+                .collect(),
+            topology: value.topology(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparse_access() {
+        let mut m = SparseMap::create(3, 4, HashMap::from([(IVec2::new(1, 2), 'x')]));
+
+        assert!(m.is_inside(IVec2::new(3, 2)));
+        assert!(!m.is_inside(IVec2::new(4, 2)));
+        assert_eq!(m.get(&IVec2::new(1, 2)), Some(&'x'));
+        assert_eq!(m.get(&IVec2::new(0, 0)), None);
+
+        assert_eq!(m.insert(IVec2::new(0, 0), 'y'), None);
+        assert_eq!(m.insert(IVec2::new(0, 0), 'z'), Some('y'));
+        assert_eq!(m.remove(&IVec2::new(1, 2)), Some('x'));
+        assert_eq!(
+            m.values_iter().collect::<Vec<_>>(),
+            vec![(IVec2::new(0, 0), &'z')]
+        );
+    }
+
+    #[test]
+    fn from_dense() {
+        let m = SparseMap::from(Map::create(1, 2, vec![1, 2]));
+
+        assert_eq!(m.row_count(), 1);
+        assert_eq!(m.col_count(), 2);
+        assert_eq!(m.get(&IVec2::new(1, 0)), Some(&2));
+    }
+
+    #[test]
+    fn wrapping() {
+        let mut m = SparseMap::create(2, 3, HashMap::new()).with_topology(Topology::Wrapping);
+
+        assert_eq!(m.insert(IVec2::new(-1, 2), 'x'), None);
+        assert_eq!(m.get(&IVec2::new(2, 0)), Some(&'x'));
+        assert_eq!(
+            m.neighbours(IVec2::new(0, 0)).collect::<Vec<_>>(),
+            vec![(IVec2::new(2, 0), &'x')]
+        );
+        assert_eq!(
+            m.neighbours8(IVec2::new(0, 1)).collect::<Vec<_>>(),
+            vec![(IVec2::new(2, 0), &'x'); 2]
+        );
+        assert_eq!(m.remove(&IVec2::new(5, 4)), Some('x'));
+        assert_eq!(m.bounds(), Some((IVec2::new(0, 0), IVec2::new(2, 1))));
+    }
+
+    #[test]
+    fn topology_after_insertion() {
+        // unreachable while bounded
+        let m = SparseMap::create(2, 3, HashMap::from([(IVec2::new(-1, 2), 'x')]));
+        assert_eq!(m.get(&IVec2::new(-1, 2)), None);
+
+        let m = m.with_topology(Topology::Wrapping);
+        assert_eq!(m.get(&IVec2::new(2, 0)), Some(&'x'));
+        assert_eq!(
+            m.values_iter().collect::<Vec<_>>(),
+            vec![(IVec2::new(2, 0), &'x')]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn topology_drops_values() {
+        SparseMap::create(1, 1, HashMap::from([(IVec2::new(3, 3), 1)]))
+            .with_topology(Topology::Bounded);
+    }
+
+    #[test]
+    fn unbounded() {
+        let mut m = SparseMap::unbounded();
+        assert_eq!(m.bounds(), None);
+
+        m.insert(IVec2::new(-5, 3), 1);
+        m.insert(IVec2::new(100, -2), 2);
+        m.insert(IVec2::new(101, -2), 3);
+
+        assert!(m.is_inside(IVec2::new(-1000, 1000)));
+        assert_eq!(m.get(&IVec2::new(-5, 3)), Some(&1));
+        assert_eq!(
+            m.neighbours(IVec2::new(100, -2)).collect::<Vec<_>>(),
+            vec![(IVec2::new(101, -2), &3)]
+        );
+        assert_eq!(m.bounds(), Some((IVec2::new(-5, -2), IVec2::new(101, 3))));
+    }
+}
@@ -0,0 +1,69 @@
+use glam::IVec2;
+
+/// How a map treats positions outside of its rows and columns.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum Topology {
+    /// Only positions within the rows and columns exist
+    #[default]
+    Bounded,
+    /// Positions wrap around the edges, like on a torus
+    Wrapping,
+    /// Every position exists. Only supported by [crate::SparseMap].
+    Unbounded,
+}
+
+/// The topologies of a dense [crate::Map], which stores every position
+/// that exists.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum DenseTopology {
+    #[default]
+    Bounded,
+    Wrapping,
+}
+
+impl From<DenseTopology> for Topology {
+    fn from(value: DenseTopology) -> Self {
+        match value {
+            DenseTopology::Bounded => Topology::Bounded,
+            DenseTopology::Wrapping => Topology::Wrapping,
+        }
+    }
+}
+
+impl Topology {
+    /// The position that `pos` refers to in a `rows` x `cols` map, if it exists.
+    pub(crate) fn normalize(&self, pos: IVec2, rows: usize, cols: usize) -> Option<IVec2> {
+        match self {
+            Topology::Bounded => {
+                (pos.x >= 0 && (pos.x as usize) < cols && pos.y >= 0 && (pos.y as usize) < rows)
+                    .then_some(pos)
+            }
+            Topology::Wrapping => {
+                (rows > 0 && cols > 0).then(|| pos.rem_euclid(IVec2::new(cols as i32, rows as i32)))
+            }
+            Topology::Unbounded => Some(pos),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize() {
+        let p = IVec2::new(-1, 7);
+
+        assert_eq!(Topology::Bounded.normalize(p, 5, 3), None);
+        assert_eq!(
+            Topology::Bounded.normalize(IVec2::new(2, 4), 5, 3),
+            Some(IVec2::new(2, 4))
+        );
+        assert_eq!(
+            Topology::Wrapping.normalize(p, 5, 3),
+            Some(IVec2::new(2, 2))
+        );
+        assert_eq!(Topology::Wrapping.normalize(p, 0, 0), None);
+        assert_eq!(Topology::Unbounded.normalize(p, 0, 0), Some(p));
+    }
+}
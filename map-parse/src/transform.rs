@@ -0,0 +1,256 @@
+use glam::IVec2;
+use std::fmt::Debug;
+
+use crate::Map;
+
+impl<T: Clone + PartialEq + Debug> Map<T> {
+    /// Builds a map by calling `value` for every position, row by row.
+    pub fn from_fn(rows: usize, cols: usize, value: impl Fn(IVec2) -> T) -> Self {
+        Map::create(
+            rows,
+            cols,
+            (0..rows)
+                .flat_map(|y| (0..cols).map(move |x| IVec2::new(x as i32, y as i32)))
+                .map(value)
+                .collect(),
+        )
+    }
+
+    /// Builds a map of the same topology by calling `value` for every
+    /// position.
+    fn transformed(&self, rows: usize, cols: usize, value: impl Fn(IVec2) -> T) -> Self {
+        Map::from_fn(rows, cols, value).with_topology(self.topology)
+    }
+
+    /// Value at a position known to be inside the map
+    fn at(&self, x: usize, y: usize) -> T {
+        self.get(&IVec2::new(x as i32, y as i32))
+            .expect("position is inside the map")
+            .clone()
+    }
+
+    /// Swaps rows and columns (mirrors along the main diagonal).
+    pub fn transpose(&self) -> Self {
+        self.transformed(self.col_count(), self.row_count(), |p| {
+            self.at(p.y as usize, p.x as usize)
+        })
+    }
+
+    /// Rotates the map 90 degrees clockwise.
+    pub fn rotate90(&self) -> Self {
+        let rows = self.row_count();
+        self.transformed(self.col_count(), rows, |p| {
+            self.at(p.y as usize, rows - 1 - p.x as usize)
+        })
+    }
+
+    pub fn rotate180(&self) -> Self {
+        let (rows, cols) = (self.row_count(), self.col_count());
+        self.transformed(rows, cols, |p| {
+            self.at(cols - 1 - p.x as usize, rows - 1 - p.y as usize)
+        })
+    }
+
+    /// Rotates the map 270 degrees clockwise (i.e. 90 degrees counter-clockwise).
+    pub fn rotate270(&self) -> Self {
+        let cols = self.col_count();
+        self.transformed(cols, self.row_count(), |p| {
+            self.at(cols - 1 - p.y as usize, p.x as usize)
+        })
+    }
+
+    /// Mirrors the map left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let cols = self.col_count();
+        self.transformed(self.row_count(), cols, |p| {
+            self.at(cols - 1 - p.x as usize, p.y as usize)
+        })
+    }
+
+    /// Mirrors the map top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let rows = self.row_count();
+        self.transformed(rows, self.col_count(), |p| {
+            self.at(p.x as usize, rows - 1 - p.y as usize)
+        })
+    }
+
+    /// Replaces every cell with `N` cells side by side, making the map `N`
+    /// times wider.
+    pub fn expand<U: Clone + PartialEq + Debug, const N: usize>(
+        &self,
+        f: impl Fn(&T) -> [U; N],
+    ) -> Map<U> {
+        Map::create(
+            self.row_count(),
+            self.col_count() * N,
+            self.values_iter().flat_map(|(_, value)| f(value)).collect(),
+        )
+        .with_topology(self.topology)
+    }
+
+    /// A read-only view of the `rows` x `cols` rectangle whose top-left
+    /// corner is at `origin`.
+    ///
+    /// Panics if the rectangle does not fit inside the map. Views without
+    /// rows or columns fit anywhere and are empty.
+    pub fn view(&self, origin: IVec2, rows: usize, cols: usize) -> MapView<'_, T> {
+        assert!(
+            rows == 0
+                || cols == 0
+                || self.is_inside(origin)
+                    && self.is_inside(origin + IVec2::new(cols as i32 - 1, rows as i32 - 1)),
+            "{}x{} view at {} does not fit in a {}x{} map",
+            rows,
+            cols,
+            origin,
+            self.row_count(),
+            self.col_count()
+        );
+        MapView {
+            map: self,
+            origin,
+            rows,
+            cols,
+        }
+    }
+}
+
+/// A rectangular part of a [Map], with positions relative to its own top-left corner.
+#[derive(Debug, Clone)]
+pub struct MapView<'a, T: Clone + PartialEq + Debug> {
+    map: &'a Map<T>,
+    origin: IVec2,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T: Clone + PartialEq + Debug> MapView<'_, T> {
+    pub fn is_inside(&self, pos: IVec2) -> bool {
+        pos.x >= 0 && (pos.x as usize) < self.cols && pos.y >= 0 && (pos.y as usize) < self.rows
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.rows
+    }
+
+    pub fn col_count(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, pos: &IVec2) -> Option<&T> {
+        if self.is_inside(*pos) {
+            self.map.get(&(self.origin + *pos))
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over all positions (relative to the view) and values, row by row.
+    pub fn values_iter(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        (0..self.rows as i32)
+            .flat_map(|y| (0..self.cols as i32).map(move |x| IVec2::new(x, y)))
+            .map(|pos| (pos, self.get(&pos).expect("position is inside the view")))
+    }
+
+    /// Copies the view into its own map.
+    pub fn to_map(&self) -> Map<T> {
+        Map::from_fn(self.rows, self.cols, |pos| {
+            self.get(&pos).expect("position is inside the view").clone()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DenseTopology, Topology};
+
+    use super::*;
+
+    fn grid(rows: usize, cols: usize, s: &str) -> Map<char> {
+        Map::create(rows, cols, s.chars().collect())
+    }
+
+    #[test]
+    fn rotations() {
+        // abc
+        // def
+        let m = grid(2, 3, "abcdef");
+
+        assert_eq!(m.transpose(), grid(3, 2, "adbecf"));
+        assert_eq!(m.rotate90(), grid(3, 2, "daebfc"));
+        assert_eq!(m.rotate180(), grid(2, 3, "fedcba"));
+        assert_eq!(m.rotate270(), grid(3, 2, "cfbead"));
+        assert_eq!(m.flip_horizontal(), grid(2, 3, "cbafed"));
+        assert_eq!(m.flip_vertical(), grid(2, 3, "defabc"));
+
+        assert_eq!(m.rotate90().rotate90(), m.rotate180());
+        assert_eq!(m.rotate90().rotate270(), m);
+        assert_eq!(m.transpose().transpose(), m);
+    }
+
+    #[test]
+    fn expand() {
+        let m = grid(2, 2, "#O.#");
+
+        assert_eq!(
+            m.expand(|c| match c {
+                'O' => ['[', ']'],
+                c => [*c, *c],
+            }),
+            grid(2, 4, "##[]..##")
+        );
+    }
+
+    #[test]
+    fn views() {
+        let m = grid(3, 4, "abcdefghijkl");
+        let v = m.view(IVec2::new(1, 1), 2, 2);
+
+        assert_eq!(v.row_count(), 2);
+        assert_eq!(v.col_count(), 2);
+        assert_eq!(v.get(&IVec2::new(0, 0)), Some(&'f'));
+        assert_eq!(v.get(&IVec2::new(1, 1)), Some(&'k'));
+        assert_eq!(v.get(&IVec2::new(2, 1)), None);
+        assert_eq!(v.get(&IVec2::new(-1, 0)), None);
+        assert_eq!(v.values_iter().map(|(_, c)| *c).collect::<String>(), "fgjk");
+        assert_eq!(v.to_map(), grid(2, 2, "fgjk"));
+    }
+
+    #[test]
+    fn keeps_topology() {
+        let m = grid(2, 3, "abcdef").with_topology(DenseTopology::Wrapping);
+
+        for transformed in [
+            m.transpose(),
+            m.rotate90(),
+            m.rotate180(),
+            m.rotate270(),
+            m.flip_horizontal(),
+            m.flip_vertical(),
+        ] {
+            assert_eq!(transformed.topology(), Topology::Wrapping);
+        }
+        assert_eq!(m.rotate90().get(&IVec2::new(2, 0)), Some(&'d'));
+        assert_eq!(m.expand(|c| [*c; 2]).topology(), Topology::Wrapping);
+    }
+
+    #[test]
+    fn empty_views() {
+        let m = grid(2, 2, "abcd");
+
+        for (rows, cols) in [(0, 0), (0, 2), (2, 0)] {
+            let v = m.view(IVec2::new(5, 5), rows, cols);
+            assert_eq!((v.row_count(), v.col_count()), (rows, cols));
+            assert_eq!(v.get(&IVec2::ZERO), None);
+            assert_eq!(v.values_iter().count(), 0);
+            assert_eq!(v.to_map(), Map::create(rows, cols, vec![]));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn view_outside() {
+        grid(2, 2, "abcd").view(IVec2::new(1, 1), 2, 1);
+    }
+}
@@ -1,63 +1,73 @@
-#[derive(thiserror::Error, Debug, PartialEq)]
-enum ProcessingError {
-    #[error("Failed to parse using Nom")]
-    NomError(#[source] nom::Err<nom::error::Error<String>>),
+use aoc_support::{
+    error::{parse_complete, ProcessingError},
+    Solution,
+};
+use nom::{
+    bytes::complete::take_till1,
+    character::complete::line_ending,
+    multi::{many0, separated_list0},
+    Parser as _,
+};
+use nom_supreme::ParserExt;
 
-    #[error("Unparsed data remained: {0:?}")]
-    UnparsedData(String),
+#[derive(Debug, PartialEq)]
+pub struct Input {
+    lines: Vec<String>,
 }
 
-struct Input {}
-
 fn parse_input(s: &str) -> Result<Input, ProcessingError> {
-    let rest = s;
+    parse_complete(
+        separated_list0(line_ending, take_till1(|c| c == '\r' || c == '\n'))
+            .terminated(many0(line_ending))
+            .map(|lines: Vec<&str>| Input {
+                lines: lines.into_iter().map(String::from).collect(),
+            }),
+        s,
+    )
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = {{day}};
 
-    if !rest.is_empty() {
-        return Err(ProcessingError::UnparsedData(rest.into()));
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+    type Params = ();
+
+    fn parse(input: &str) -> color_eyre::Result<Input> {
+        Ok(parse_input(input)?)
     }
 
-    Ok(Input {})
-}
+    fn part1(input: &Input, _: &()) -> color_eyre::Result<usize> {
+        Ok(input.lines.len())
+    }
 
-impl<INNER: Into<String>> From<nom::Err<nom::error::Error<INNER>>> for ProcessingError {
-    fn from(value: nom::Err<nom::error::Error<INNER>>) -> Self {
-        ProcessingError::NomError(value.map_input(|i| i.into()))
+    fn part2(input: &Input, _: &()) -> color_eyre::Result<usize> {
+        Ok(input.lines.len())
     }
 }
 
 pub fn part1(input: &str) -> color_eyre::Result<usize> {
-    let mut input = parse_input(input)?;
-
-    todo!();
+    Solver::solve1(input, &())
 }
 
 pub fn part2(input: &str) -> color_eyre::Result<usize> {
-    let mut input = parse_input(input)?;
-
-    todo!();
+    Solver::solve2(input, &())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use std::sync::Once;
+    use aoc_support::testing::init_tests;
 
-    static INIT: Once = Once::new();
-    pub fn init_tests() {
-        INIT.call_once(|| {
-            color_eyre::install().unwrap_or(());
-        });
-    }
+    use super::*;
 
     #[test]
-    fn test_part1() {
+    fn test_parse() {
         init_tests();
-        assert_eq!(part1(include_str!("../example.txt")).expect("success"), 0);
+        assert!(parse_input(include_str!("../example.txt")).is_ok());
     }
 
-    #[test]
-    fn test_part2() {
-        init_tests();
-        assert_eq!(part2(include_str!("../example.txt")).expect("success"), 0);
-    }
+    aoc_support::example_tests!(Solver);
 }